@@ -0,0 +1,308 @@
+// 組み込みのファイル種別カタログ
+//
+// よく使われるファイルの種類をカテゴリごとにまとめた組み込みのカタログです。
+// 新しい設定の既定のカテゴリはこのカタログから作ります。カテゴリのフォルダ名とは別に
+// 日本語・英語の表示名を持ち、画面の言語に合わせて表示できます。
+// アプリの更新でカタログに追加された拡張子は、ユーザーの編集を上書きせずに
+// 既存の設定へ取り込めます。
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+use crate::{extensions, Config};
+
+/// カタログのカテゴリ
+pub struct CatalogueCategory {
+    /// カテゴリ名（フォルダ名）
+    pub folder: &'static str,
+    /// 日本語の表示名
+    pub name_ja: &'static str,
+    /// 英語の表示名
+    pub name_en: &'static str,
+    /// 対象の拡張子
+    pub extensions: &'static [&'static str],
+}
+
+/// 組み込みのカタログ
+pub const CATALOGUE: &[CatalogueCategory] = &[
+    CatalogueCategory {
+        folder: "Images",
+        name_ja: "画像",
+        name_en: "Images",
+        extensions: &[
+            ".jpg", ".jpeg", ".png", ".gif", ".bmp", ".webp", ".heic", ".heif", ".avif", ".tiff",
+            ".svg", ".ico", ".raw", ".dng", ".cr2", ".cr3", ".nef", ".arw", ".orf", ".rw2",
+        ],
+    },
+    CatalogueCategory {
+        folder: "Documents",
+        name_ja: "文書",
+        name_en: "Documents",
+        extensions: &[
+            ".pdf", ".doc", ".docx", ".txt", ".rtf", ".odt", ".md", ".pages", ".tex",
+        ],
+    },
+    CatalogueCategory {
+        folder: "Spreadsheets",
+        name_ja: "表計算",
+        name_en: "Spreadsheets",
+        extensions: &[".xlsx", ".xls", ".xlsm", ".csv", ".tsv", ".ods", ".numbers"],
+    },
+    CatalogueCategory {
+        folder: "Presentations",
+        name_ja: "プレゼンテーション",
+        name_en: "Presentations",
+        extensions: &[".pptx", ".ppt", ".odp", ".key"],
+    },
+    CatalogueCategory {
+        folder: "Ebooks",
+        name_ja: "電子書籍",
+        name_en: "E-books",
+        extensions: &[".epub", ".mobi", ".azw", ".azw3", ".fb2", ".djvu"],
+    },
+    CatalogueCategory {
+        folder: "Videos",
+        name_ja: "動画",
+        name_en: "Videos",
+        extensions: &[
+            ".mp4", ".avi", ".mov", ".wmv", ".mkv", ".webm", ".flv", ".m4v", ".mpg", ".3gp",
+            ".m2ts",
+        ],
+    },
+    CatalogueCategory {
+        folder: "Audio",
+        name_ja: "音声",
+        name_en: "Audio",
+        extensions: &[
+            ".mp3", ".wav", ".ogg", ".flac", ".aac", ".m4a", ".wma", ".opus", ".aiff", ".alac",
+            ".mid",
+        ],
+    },
+    CatalogueCategory {
+        folder: "Archives",
+        name_ja: "圧縮ファイル",
+        name_en: "Archives",
+        extensions: &[
+            ".zip", ".rar", ".7z", ".tar", ".gz", ".bz2", ".xz", ".zst", ".lzh", ".cab", ".tar.gz",
+            ".tar.bz2", ".tar.xz", ".tar.zst",
+        ],
+    },
+    CatalogueCategory {
+        folder: "DiskImages",
+        name_ja: "ディスクイメージ",
+        name_en: "Disk images",
+        extensions: &[".dmg", ".iso", ".img", ".vhd", ".vhdx", ".vmdk", ".qcow2"],
+    },
+    CatalogueCategory {
+        folder: "Installers",
+        name_ja: "インストーラー",
+        name_en: "Installers",
+        extensions: &[
+            ".exe",
+            ".msi",
+            ".msix",
+            ".pkg",
+            ".deb",
+            ".rpm",
+            ".apk",
+            ".appimage",
+            ".flatpakref",
+        ],
+    },
+    CatalogueCategory {
+        folder: "Code",
+        name_ja: "ソースコード",
+        name_en: "Source code",
+        extensions: &[
+            ".rs", ".py", ".js", ".mjs", ".jsx", ".ts", ".tsx", ".java", ".kt", ".c", ".h", ".cpp",
+            ".hpp", ".cs", ".go", ".rb", ".php", ".swift", ".sh", ".ps1", ".bat", ".sql", ".html",
+            ".css", ".scss", ".json", ".yaml", ".toml", ".xml", ".ipynb",
+        ],
+    },
+    CatalogueCategory {
+        folder: "Fonts",
+        name_ja: "フォント",
+        name_en: "Fonts",
+        extensions: &[".ttf", ".otf", ".ttc", ".woff", ".woff2"],
+    },
+    CatalogueCategory {
+        folder: "3DModels",
+        name_ja: "3Dモデル",
+        name_en: "3D models",
+        extensions: &[
+            ".stl", ".obj", ".fbx", ".blend", ".gltf", ".glb", ".3mf", ".step", ".stp", ".dae",
+        ],
+    },
+    CatalogueCategory {
+        folder: "Design",
+        name_ja: "デザイン",
+        name_en: "Design",
+        extensions: &[
+            ".psd", ".ai", ".eps", ".sketch", ".fig", ".xd", ".indd", ".xcf",
+        ],
+    },
+];
+
+/// カテゴリの表示名
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DisplayName {
+    /// 日本語の表示名
+    pub ja: String,
+    /// 英語の表示名
+    pub en: String,
+}
+
+/// カタログから取り込んだカテゴリ
+#[derive(Serialize, Clone, Debug)]
+pub struct CatalogueAddition {
+    /// カテゴリ名
+    pub category: String,
+    /// 新しく作ったカテゴリか
+    pub new_category: bool,
+    /// 追加した拡張子
+    pub extensions: Vec<String>,
+}
+
+/// カタログの取り込み結果
+#[derive(Serialize, Clone, Debug)]
+pub struct CatalogueMergeResult {
+    /// 取り込み後の設定
+    pub config: Config,
+    /// 取り込んだ内容
+    pub added: Vec<CatalogueAddition>,
+}
+
+/// カタログのカテゴリ（既定の設定用）
+pub fn default_categories() -> HashMap<String, Vec<String>> {
+    CATALOGUE
+        .iter()
+        .map(|category| {
+            (
+                category.folder.to_string(),
+                category
+                    .extensions
+                    .iter()
+                    .map(|ext| ext.to_string())
+                    .collect(),
+            )
+        })
+        .collect()
+}
+
+/// カタログの表示名（既定の設定用）
+pub fn default_display_names() -> HashMap<String, DisplayName> {
+    CATALOGUE
+        .iter()
+        .map(|category| {
+            (
+                category.folder.to_string(),
+                DisplayName {
+                    ja: category.name_ja.to_string(),
+                    en: category.name_en.to_string(),
+                },
+            )
+        })
+        .collect()
+}
+
+/// カタログのすべての拡張子（既定の設定用）
+pub fn all_extensions() -> Vec<String> {
+    CATALOGUE
+        .iter()
+        .flat_map(|category| category.extensions.iter().map(|ext| ext.to_string()))
+        .collect()
+}
+
+/// カタログの新しい項目を設定に取り込む
+///
+/// 既に取り込んだことのある拡張子（ユーザーが削除したものを含む）と、
+/// いずれかのカテゴリに割り当て済みの拡張子は追加しません。
+/// 既存のカテゴリや表示名は変更しません。
+pub fn merge_into(config: &mut Config) -> Vec<CatalogueAddition> {
+    let aliases = config.extension_aliases.clone();
+    let mut seen: HashSet<String> = config
+        .catalogue_extensions
+        .iter()
+        .map(|ext| extensions::canonical(ext, &aliases))
+        .collect();
+    let mut assigned: HashSet<String> = config
+        .categories
+        .values()
+        .flatten()
+        .map(|ext| extensions::canonical(ext, &aliases))
+        .collect();
+
+    let mut added = Vec::new();
+    for entry in CATALOGUE {
+        let mut new_extensions = Vec::new();
+        for ext in entry.extensions {
+            let canonical = extensions::canonical(ext, &aliases);
+            if !seen.insert(canonical.clone()) {
+                continue;
+            }
+            config.catalogue_extensions.push(ext.to_string());
+            if assigned.insert(canonical) {
+                new_extensions.push(ext.to_string());
+            }
+        }
+
+        if !new_extensions.is_empty() {
+            let category = target_category(config, entry, &aliases);
+            let new_category = !config.categories.contains_key(&category);
+            config
+                .categories
+                .entry(category.clone())
+                .or_default()
+                .extend(new_extensions.iter().cloned());
+            added.push(CatalogueAddition {
+                category,
+                new_category,
+                extensions: new_extensions,
+            });
+        }
+
+        if config.categories.contains_key(entry.folder) {
+            config
+                .display_names
+                .entry(entry.folder.to_string())
+                .or_insert_with(|| DisplayName {
+                    ja: entry.name_ja.to_string(),
+                    en: entry.name_en.to_string(),
+                });
+        }
+    }
+    added
+}
+
+/// カタログのカテゴリの追加先
+///
+/// ユーザーがカテゴリ名を変えている場合に備え、同名のカテゴリがなければ
+/// カタログの拡張子を最も多く含むカテゴリに追加します
+pub fn target_category(
+    config: &Config,
+    entry: &CatalogueCategory,
+    aliases: &HashMap<String, String>,
+) -> String {
+    if config.categories.contains_key(entry.folder) {
+        return entry.folder.to_string();
+    }
+    let catalogue: HashSet<String> = entry
+        .extensions
+        .iter()
+        .map(|ext| extensions::canonical(ext, aliases))
+        .collect();
+    config
+        .categories
+        .iter()
+        .map(|(category, exts)| {
+            let count = exts
+                .iter()
+                .filter(|ext| catalogue.contains(&extensions::canonical(ext, aliases)))
+                .count();
+            (category, count)
+        })
+        .filter(|(_, count)| *count > 0)
+        .max_by(|(a, a_count), (b, b_count)| a_count.cmp(b_count).then_with(|| b.cmp(a)))
+        .map(|(category, _)| category.clone())
+        .unwrap_or_else(|| entry.folder.to_string())
+}
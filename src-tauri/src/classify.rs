@@ -0,0 +1,310 @@
+// カテゴリの判定
+//
+// ファイルをまず拡張子で親カテゴリに振り分け、親カテゴリにサブカテゴリのルールがあれば
+// ファイル名のパターンや拡張子でさらに細かく振り分けます（Documents/Invoicesなど）。
+// カテゴリのパスは`/`区切りで表し、出力先ではフォルダの階層になります。
+// どのカテゴリにも当てはまらないファイルの扱いは設定で選べます。
+
+use glob::{MatchOptions, Pattern};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+
+use crate::extensions;
+use crate::{get_category, Config, DEFAULT_CATEGORY};
+
+/// カテゴリのパスの区切り文字
+pub const CATEGORY_SEPARATOR: char = '/';
+
+/// 確認待ちフォルダの既定の名前
+const DEFAULT_REVIEW_FOLDER: &str = "_Review";
+
+/// 拡張子のないファイルを拡張子ごとに分ける場合のフォルダ名
+const NO_EXTENSION_FOLDER: &str = "_none";
+
+/// どのカテゴリにも当てはまらないファイルの扱い
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FallbackMode {
+    /// 未分類フォルダに移動する
+    #[default]
+    Folder,
+    /// 未分類フォルダの中で拡張子ごとのフォルダに分ける（`Others/xyz`）
+    ByExtension,
+    /// 移動せずにそのまま残す
+    LeaveInPlace,
+    /// 確認待ちフォルダに移動し、後で仕分けられるようにする
+    Review,
+}
+
+/// どのカテゴリにも当てはまらないファイルの扱いの設定
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct FallbackOptions {
+    /// 扱い方
+    pub mode: FallbackMode,
+    /// 未分類フォルダの名前
+    pub folder_name: String,
+    /// 確認待ちフォルダの名前
+    pub review_folder_name: String,
+}
+
+impl Default for FallbackOptions {
+    fn default() -> Self {
+        Self {
+            mode: FallbackMode::default(),
+            folder_name: DEFAULT_CATEGORY.to_string(),
+            review_folder_name: DEFAULT_REVIEW_FOLDER.to_string(),
+        }
+    }
+}
+
+impl FallbackOptions {
+    /// 当てはまらなかったファイルの移動先カテゴリ（移動しない場合はNone）
+    fn category_for(&self, ext: &str) -> Option<String> {
+        match self.mode {
+            FallbackMode::Folder => Some(self.folder_name.clone()),
+            FallbackMode::ByExtension => {
+                let ext = ext.trim_start_matches('.');
+                let ext = if ext.is_empty() {
+                    NO_EXTENSION_FOLDER
+                } else {
+                    ext
+                };
+                Some(format!("{}{}{}", self.folder_name, CATEGORY_SEPARATOR, ext))
+            }
+            FallbackMode::LeaveInPlace => None,
+            FallbackMode::Review => Some(self.review_folder_name.clone()),
+        }
+    }
+
+    /// フォルダ名を確認（出力先の直下に作れない名前はエラー）
+    pub fn validate(&self) -> std::result::Result<(), String> {
+        validate_folder_name("未分類フォルダ", &self.folder_name)?;
+        validate_folder_name("確認待ちフォルダ", &self.review_folder_name)
+    }
+
+    /// 出力先に作られる未分類・確認待ちフォルダの名前
+    pub fn folder_names(&self) -> Vec<String> {
+        match self.mode {
+            FallbackMode::Folder | FallbackMode::ByExtension => vec![self.folder_name.clone()],
+            FallbackMode::LeaveInPlace => vec![],
+            FallbackMode::Review => vec![self.review_folder_name.clone()],
+        }
+    }
+}
+
+/// サブカテゴリのルール
+///
+/// 拡張子とファイル名のパターンの両方を指定した場合は、両方に一致したファイルが対象です
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct Subcategory {
+    /// サブカテゴリ名（フォルダ名）
+    pub name: String,
+    /// 対象とする拡張子（空の場合は拡張子で絞り込まない）
+    #[serde(default)]
+    pub extensions: Vec<String>,
+    /// 対象とするファイル名のパターン（`*請求書*`など、大文字小文字は区別しない）
+    #[serde(default)]
+    pub name_patterns: Vec<String>,
+    /// さらに細かいサブカテゴリ
+    #[serde(default)]
+    pub children: Vec<Subcategory>,
+}
+
+impl Subcategory {
+    /// サブカテゴリ名（子のサブカテゴリを含む）を確認
+    pub fn validate(&self) -> std::result::Result<(), String> {
+        validate_folder_name("サブカテゴリ", &self.name)?;
+        self.children.iter().try_for_each(Subcategory::validate)
+    }
+
+    /// ファイルがこのサブカテゴリに当てはまるか（`ext`は代表的な拡張子）
+    fn matches(&self, file_name: &str, ext: &str, aliases: &HashMap<String, String>) -> bool {
+        if self.extensions.is_empty() && self.name_patterns.is_empty() {
+            return false;
+        }
+        let ext_matches = self.extensions.is_empty()
+            || self
+                .extensions
+                .iter()
+                .any(|e| extensions::canonical(e, aliases) == ext);
+        let name_matches = self.name_patterns.is_empty()
+            || self
+                .name_patterns
+                .iter()
+                .any(|pattern| matches_name(pattern, file_name));
+        ext_matches && name_matches
+    }
+}
+
+impl Config {
+    /// ファイルの振り分け先カテゴリのパスを取得
+    ///
+    /// どのカテゴリにも当てはまらない場合は設定に従い、移動しない場合はNoneを返します
+    pub fn category_for(&self, file: &Path) -> Option<String> {
+        let file_name = file
+            .file_name()
+            .map(|name| name.to_string_lossy())
+            .unwrap_or_default();
+        let Some((category, ext)) = self.match_extension(&file_name, self.categories_for(file))
+        else {
+            let ext = extensions::canonical(&extensions::last(&file_name), &self.extension_aliases);
+            return self.fallback.category_for(&ext);
+        };
+
+        Some(refine(
+            category,
+            &self.subcategories,
+            &file_name,
+            &ext,
+            &self.extension_aliases,
+        ))
+    }
+
+    /// 拡張子に当てはまるカテゴリと、当てはまった代表的な拡張子を取得
+    ///
+    /// 複合拡張子は長いものから順に判定し、当てはまらないバックアップファイルは
+    /// 元のファイル名で判定します
+    fn match_extension(
+        &self,
+        file_name: &str,
+        categories: &HashMap<String, Vec<String>>,
+    ) -> Option<(String, String)> {
+        extensions::candidates(file_name)
+            .into_iter()
+            .find_map(|ext| {
+                let ext = extensions::canonical(&ext, &self.extension_aliases);
+                get_category(&ext, categories, &self.extension_aliases)
+                    .map(|category| (category, ext))
+            })
+            .or_else(|| {
+                let original = extensions::strip_backup_suffix(file_name)?;
+                self.match_extension(original, categories)
+            })
+    }
+}
+
+/// 親カテゴリをサブカテゴリのルールで細分化し、カテゴリのパスを返す
+///
+/// 各階層では最初に当てはまったサブカテゴリを採用します
+pub fn refine(
+    category: String,
+    subcategories: &HashMap<String, Vec<Subcategory>>,
+    file_name: &str,
+    ext: &str,
+    aliases: &HashMap<String, String>,
+) -> String {
+    let Some(mut children) = subcategories.get(&category).map(Vec::as_slice) else {
+        return category;
+    };
+
+    let mut path = category;
+    while let Some(child) = children
+        .iter()
+        .find(|child| child.matches(file_name, ext, aliases))
+    {
+        path.push(CATEGORY_SEPARATOR);
+        path.push_str(&child.name);
+        children = &child.children;
+    }
+    path
+}
+
+/// カテゴリのパスに対応するフォルダ
+///
+/// 出力先の外に出ないよう、`.`や`..`、ドライブ名などフォルダ名にならない部分は無視します
+pub fn category_dir(output_root: &Path, category: &str) -> PathBuf {
+    category
+        .split(CATEGORY_SEPARATOR)
+        .filter(|part| {
+            let mut components = Path::new(part).components();
+            matches!(
+                (components.next(), components.next()),
+                (Some(Component::Normal(_)), None)
+            )
+        })
+        .fold(output_root.to_path_buf(), |dir, part| dir.join(part))
+}
+
+/// フォルダ名を確認（空の名前や、区切り文字・ドライブ名・`..`を含む名前はエラー）
+pub fn validate_folder_name(label: &str, name: &str) -> std::result::Result<(), String> {
+    let trimmed = name.trim();
+    if trimmed.is_empty() {
+        return Err(format!("{}の名前を入力してください", label));
+    }
+    if trimmed == "." || trimmed == ".." || trimmed.contains(['/', '\\', ':']) {
+        return Err(format!(
+            "{}の名前に使用できない文字が含まれています: {}",
+            label, name
+        ));
+    }
+    Ok(())
+}
+
+/// カテゴリのパスを、自身から親カテゴリへ順に列挙
+///
+/// `Documents/Invoices`の場合は`Documents/Invoices`、`Documents`の順です
+pub fn ancestors(category: &str) -> impl Iterator<Item = &str> {
+    std::iter::successors(Some(category), |current| {
+        current
+            .rfind(CATEGORY_SEPARATOR)
+            .map(|index| &current[..index])
+    })
+}
+
+/// ファイル名がパターンに一致するか（大文字小文字は区別しない）
+pub fn matches_name(pattern: &str, file_name: &str) -> bool {
+    let options = MatchOptions {
+        case_sensitive: false,
+        ..MatchOptions::default()
+    };
+    Pattern::new(pattern)
+        .map(|pattern| pattern.matches_with(file_name, options))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn folder_names_outside_the_output_root_are_rejected() {
+        for name in ["", " ", ".", "..", "a/b", "a\\b", "C:", "C:\\Others"] {
+            assert!(
+                validate_folder_name("未分類フォルダ", name).is_err(),
+                "{name}"
+            );
+        }
+        assert!(validate_folder_name("未分類フォルダ", "Others").is_ok());
+    }
+
+    #[test]
+    fn nested_subcategory_names_are_validated() {
+        let subcategory = Subcategory {
+            name: "Invoices".to_string(),
+            extensions: vec![],
+            name_patterns: vec![],
+            children: vec![Subcategory {
+                name: "..".to_string(),
+                extensions: vec![],
+                name_patterns: vec![],
+                children: vec![],
+            }],
+        };
+        assert!(subcategory.validate().is_err());
+    }
+
+    #[test]
+    fn category_dir_stays_inside_the_output_root() {
+        let root = Path::new("out");
+        assert_eq!(
+            category_dir(root, "Documents/Invoices"),
+            root.join("Documents").join("Invoices")
+        );
+        assert_eq!(
+            category_dir(root, "../Documents/./"),
+            root.join("Documents")
+        );
+    }
+}
@@ -0,0 +1,107 @@
+// 移動先のファイル名の衝突の判定
+//
+// macOSから来たファイル名はNFD、Windows・LinuxではNFCのことが多く、
+// 見た目が同じでもバイト列が異なるため単純な存在確認では衝突を見逃します。
+// 移動先フォルダの既存のファイル名と正規化した名前で比較し、
+// 大文字小文字を区別しない移動先（exFAT、SMBなど）向けに大文字小文字を無視した比較もできます。
+// 移動先フォルダの既存のファイル名は整理ジョブごとに一度だけ読み込みます。
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::ffi::{OsStr, OsString};
+use std::fs;
+use std::path::{Path, PathBuf};
+use unicode_normalization::UnicodeNormalization;
+
+use crate::numbered_file_name;
+
+/// 衝突の判定の設定
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct CollisionOptions {
+    /// 大文字小文字を区別せずに衝突を判定する
+    pub case_insensitive: bool,
+    /// 移動時にファイル名をNFCに正規化する
+    pub normalize_names: bool,
+}
+
+/// 移動先フォルダごとの既存のファイル名（比較用に正規化したもの）
+///
+/// 整理ジョブごとに作り、フォルダの読み込みは最初の1回だけ行います
+#[derive(Default)]
+pub struct NameIndex {
+    dirs: HashMap<PathBuf, HashSet<String>>,
+}
+
+impl CollisionOptions {
+    /// 比較用に正規化したファイル名（UTF-8として解釈できない名前はそのまま比較する）
+    fn key(&self, name: &OsStr) -> String {
+        let Some(name) = name.to_str() else {
+            return name.to_string_lossy().into_owned();
+        };
+        let normalized: String = name.nfc().collect();
+        if self.case_insensitive {
+            normalized.to_lowercase()
+        } else {
+            normalized
+        }
+    }
+
+    /// 移動先で使うファイル名（設定に応じてNFCに正規化）
+    pub fn file_name(&self, name: OsString) -> OsString {
+        if !self.normalize_names {
+            return name;
+        }
+        match name.to_str() {
+            Some(utf8) => OsString::from(utf8.nfc().collect::<String>()),
+            None => name,
+        }
+    }
+
+    /// フォルダ内で衝突しない移動先のパス
+    ///
+    /// 既存のファイルと正規化した名前が一致する場合は連番を付けます
+    pub fn destination(
+        &self,
+        index: &mut NameIndex,
+        dir: &Path,
+        file_name: &OsStr,
+        compounds: &[String],
+    ) -> PathBuf {
+        let existing = index
+            .dirs
+            .entry(dir.to_path_buf())
+            .or_insert_with(|| self.read_keys(dir));
+        let taken = |name: &OsStr| dir.join(name).exists() || existing.contains(&self.key(name));
+
+        let mut dest_path = dir.join(file_name);
+        let mut counter = 1;
+        while taken(dest_path.file_name().unwrap_or_default()) {
+            dest_path = dir.join(numbered_file_name(Path::new(file_name), counter, compounds));
+            counter += 1;
+        }
+        dest_path
+    }
+
+    /// 移動したファイルの名前を記録（以降のファイルとの衝突の判定に使う）
+    pub fn record(&self, index: &mut NameIndex, dest_path: &Path) {
+        let (Some(dir), Some(name)) = (dest_path.parent(), dest_path.file_name()) else {
+            return;
+        };
+        if let Some(existing) = index.dirs.get_mut(dir) {
+            existing.insert(self.key(name));
+        }
+    }
+
+    /// フォルダ内の既存のファイル名を比較用に正規化して読み込む
+    fn read_keys(&self, dir: &Path) -> HashSet<String> {
+        fs::read_dir(dir)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .map(|entry| self.key(&entry.file_name()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
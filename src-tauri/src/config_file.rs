@@ -0,0 +1,111 @@
+// 設定ファイルの安全な保存
+//
+// 設定ファイルは一時ファイルに書き込んでから置き換えることで、保存中のクラッシュや
+// 電源断で壊れないようにします。保存のたびに直前の設定を世代管理のバックアップとして
+// 残し、設定ファイルが壊れていた場合はバックアップから復元できるようにします。
+
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::Result;
+
+/// 保持するバックアップの世代数
+pub const MAX_CONFIG_BACKUPS: usize = 5;
+
+/// 世代`generation`（1が最新）のバックアップのパス
+///
+/// `config.json.bak.1`のように設定ファイル名に世代番号を付けます
+pub fn backup_path(config_path: &Path, generation: usize) -> PathBuf {
+    with_suffix(config_path, &format!(".bak.{}", generation))
+}
+
+/// 存在するバックアップを新しい順に取得
+pub fn backups(config_path: &Path) -> Vec<PathBuf> {
+    (1..=MAX_CONFIG_BACKUPS)
+        .map(|generation| backup_path(config_path, generation))
+        .filter(|path| path.is_file())
+        .collect()
+}
+
+/// 読み込めなかった設定ファイルを退避
+///
+/// 復元時に上書きされないよう`config.json.corrupt`としてコピーします
+pub fn keep_corrupt(config_path: &Path) -> Result<PathBuf> {
+    let corrupt_path = with_suffix(config_path, ".corrupt");
+    fs::copy(config_path, &corrupt_path)?;
+    Ok(corrupt_path)
+}
+
+/// 現在の設定ファイルをバックアップの最新世代として保存し、古い世代を破棄
+///
+/// JSONとして読めない設定ファイルは、正常なバックアップを押し出さないよう残しません
+pub fn rotate_backups(config_path: &Path) -> Result<()> {
+    let is_valid = fs::read_to_string(config_path)
+        .ok()
+        .and_then(|contents| serde_json::from_str::<serde_json::Value>(&contents).ok())
+        .is_some();
+    if !is_valid {
+        return Ok(());
+    }
+
+    let oldest = backup_path(config_path, MAX_CONFIG_BACKUPS);
+    if oldest.exists() {
+        fs::remove_file(&oldest)?;
+    }
+    for generation in (1..MAX_CONFIG_BACKUPS).rev() {
+        let from = backup_path(config_path, generation);
+        if from.exists() {
+            fs::rename(&from, backup_path(config_path, generation + 1))?;
+        }
+    }
+    fs::copy(config_path, backup_path(config_path, 1))?;
+    Ok(())
+}
+
+/// ファイルを安全に書き込む
+///
+/// 同じフォルダの一時ファイルに書き込んでディスクに反映させてから、
+/// 元のファイルを置き換えます。途中で失敗した場合は元のファイルが残ります
+pub fn write_atomic(path: &Path, contents: &[u8]) -> Result<()> {
+    let temp_path = with_suffix(path, &format!(".tmp.{}", std::process::id()));
+
+    let written: Result<()> = (|| {
+        let mut file = File::create(&temp_path)?;
+        file.write_all(contents)?;
+        file.sync_all()?;
+        fs::rename(&temp_path, path)?;
+        Ok(())
+    })();
+    if written.is_err() {
+        let _ = fs::remove_file(&temp_path);
+        return written;
+    }
+
+    sync_parent_dir(path);
+    Ok(())
+}
+
+/// 置き換えたファイルのあるフォルダをディスクに反映
+#[cfg(unix)]
+fn sync_parent_dir(path: &Path) {
+    if let Some(parent) = path.parent() {
+        if let Ok(dir) = File::open(parent) {
+            let _ = dir.sync_all();
+        }
+    }
+}
+
+/// 置き換えたファイルのあるフォルダをディスクに反映（Windowsでは不要）
+#[cfg(not(unix))]
+fn sync_parent_dir(_path: &Path) {}
+
+/// ファイル名の末尾に文字列を付けたパス
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path
+        .file_name()
+        .map(|name| name.to_os_string())
+        .unwrap_or_default();
+    name.push(suffix);
+    path.with_file_name(name)
+}
@@ -0,0 +1,124 @@
+// 設定ファイルの変更監視
+//
+// 手作業での編集や同期ツールによって設定ファイルが書き換えられた場合に、
+// 設定を読み直して`config-changed`イベントで通知します。エディタによる置き換え保存にも
+// 対応できるよう、ファイルの更新日時とサイズを一定間隔で確認します。
+// アプリ自身の読み書きは通知しません。
+
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, SystemTime};
+use tauri::Emitter;
+
+use crate::folder_check::{self, FolderIssue};
+use crate::{get_config_path, parse_config_file, AppError, Config};
+
+/// 設定変更イベント名
+const CONFIG_CHANGED_EVENT: &str = "config-changed";
+
+/// 設定ファイルを確認する間隔（ミリ秒）
+const WATCH_INTERVAL_MS: u64 = 1000;
+
+/// 最後に確認した設定ファイルの状態
+static LAST_SEEN: Mutex<Option<FileStamp>> = Mutex::new(None);
+
+/// 設定ファイルの状態
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct FileStamp {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    len: Option<u64>,
+}
+
+impl FileStamp {
+    fn of(path: &Path) -> Self {
+        let metadata = fs::metadata(path).ok();
+        Self {
+            path: path.to_path_buf(),
+            modified: metadata.as_ref().and_then(|m| m.modified().ok()),
+            len: metadata.map(|m| m.len()),
+        }
+    }
+}
+
+/// 設定ファイルの変更通知
+#[derive(Serialize, Clone, Debug)]
+pub struct ConfigChanged {
+    /// 読み直した設定（読み込めなかった場合はNone）
+    pub config: Option<Config>,
+    /// 保存済みフォルダの問題
+    pub folder_issues: Vec<FolderIssue>,
+    /// 読み込めなかった場合のエラー
+    pub error: Option<String>,
+}
+
+/// アプリ自身が設定ファイルを読み書きしたことを記録（変更として通知しない）
+pub fn remember(path: &Path) {
+    *lock_last_seen() = Some(FileStamp::of(path));
+}
+
+/// 設定ファイルの監視を開始
+pub fn start(app: tauri::AppHandle) {
+    thread::spawn(move || loop {
+        thread::sleep(Duration::from_millis(WATCH_INTERVAL_MS));
+
+        let Ok(path) = get_config_path() else {
+            continue;
+        };
+        let stamp = FileStamp::of(&path);
+        {
+            let mut last_seen = lock_last_seen();
+            match last_seen.as_ref() {
+                Some(last) if last == &stamp => continue,
+                // 起動直後やプロファイル切り替え後は現在の状態を記録するだけにする
+                Some(last) if last.path == path => {}
+                _ => {
+                    *last_seen = Some(stamp);
+                    continue;
+                }
+            }
+            *last_seen = Some(stamp.clone());
+        }
+
+        // 削除された場合は、再び作成されるまで通知しない
+        if stamp.len.is_none() {
+            continue;
+        }
+
+        let _ = app.emit(CONFIG_CHANGED_EVENT, reload(&path));
+    });
+}
+
+/// 変更された設定ファイルを読み直して検証
+///
+/// 不正な内容の場合は設定を反映せず、エラーとして通知します
+fn reload(path: &Path) -> ConfigChanged {
+    let parsed = parse_config_file(path).and_then(|(config, _)| {
+        config
+            .validate()
+            .map_err(|e| AppError::Custom(format!("設定ファイルの内容が不正です: {}", e)))?;
+        Ok(config)
+    });
+    match parsed {
+        Ok(config) => ConfigChanged {
+            folder_issues: folder_check::check_folders(&config),
+            config: Some(config),
+            error: None,
+        },
+        Err(e) => ConfigChanged {
+            config: None,
+            folder_issues: Vec::new(),
+            error: Some(format!(
+                "設定ファイルの変更を反映できません（以前の設定を使用します）: {}",
+                e
+            )),
+        },
+    }
+}
+
+fn lock_last_seen() -> std::sync::MutexGuard<'static, Option<FileStamp>> {
+    LAST_SEEN.lock().unwrap_or_else(|e| e.into_inner())
+}
@@ -0,0 +1,241 @@
+// 拡張子の判定
+//
+// `archive.tar.gz`のように複数の拡張子を持つファイル名から、長いものから順に拡張子の
+// 候補を取り出します。また`.jpe`や`.jfif`のような別名は代表的な拡張子にそろえてから
+// 比較します。別名は設定で追加・上書きできます。
+
+use std::collections::HashMap;
+
+use crate::{catalogue, Config};
+
+/// 複合拡張子として扱う拡張子の最大数（`.tar.gz`は2つ）
+const MAX_EXTENSION_PARTS: usize = 3;
+
+/// 拡張子の一部とみなす長さの上限（これより長い部分はファイル名の一部とみなす）
+const MAX_EXTENSION_PART_LEN: usize = 10;
+
+/// バックアップファイルの拡張子
+///
+/// どのカテゴリにも当てはまらない場合は、これを取り除いた元のファイル名で判定します
+const BACKUP_SUFFIXES: &[&str] = &[".bak", ".backup", ".old", ".orig"];
+
+/// 組み込みの拡張子の別名（別名, 代表的な拡張子）
+const BUILTIN_ALIASES: &[(&str, &str)] = &[
+    (".jpeg", ".jpg"),
+    (".jpe", ".jpg"),
+    (".jfif", ".jpg"),
+    (".tif", ".tiff"),
+    (".htm", ".html"),
+    (".yml", ".yaml"),
+    (".markdown", ".md"),
+    (".mpeg", ".mpg"),
+    (".midi", ".mid"),
+    (".tgz", ".tar.gz"),
+    (".tbz", ".tar.bz2"),
+    (".tbz2", ".tar.bz2"),
+    (".txz", ".tar.xz"),
+    (".tzst", ".tar.zst"),
+];
+
+/// ファイル名から拡張子の候補を長い順に取得（小文字）
+///
+/// `Archive.TAR.GZ`の場合は`.tar.gz`、`.gz`の順です。
+/// 先頭が`.`の隠しファイル名（`.bashrc`など）は拡張子として扱いません
+pub fn candidates(file_name: &str) -> Vec<String> {
+    let name = file_name.to_lowercase();
+    let body = name.trim_start_matches('.');
+    let parts: Vec<&str> = body.split('.').skip(1).collect();
+
+    let mut candidates = Vec::new();
+    for count in (1..=parts.len().min(MAX_EXTENSION_PARTS)).rev() {
+        let suffix = &parts[parts.len() - count..];
+        let valid = suffix.iter().all(|part| {
+            !part.is_empty() && part.len() <= MAX_EXTENSION_PART_LEN && !part.contains(' ')
+        });
+        if valid {
+            candidates.push(format!(".{}", suffix.join(".")));
+        }
+    }
+    candidates
+}
+
+/// ファイル名を拡張子の前後に分ける
+///
+/// `compounds`に含まれる複合拡張子（`.tar.gz`など）はまとめて扱い、それ以外は
+/// `Path::extension`と同様に最後の`.`で分けます（`John.Smith.CV.pdf` → `John.Smith.CV`、`.pdf`）。
+/// 先頭の`.`だけの名前（`.bashrc`）は拡張子なしとします
+pub fn split<'a>(name: &'a str, compounds: &[String]) -> (&'a str, &'a str) {
+    let lower = name.to_lowercase();
+    let compound = candidates(name)
+        .into_iter()
+        .filter(|ext| ext.matches('.').count() > 1 && compounds.contains(ext))
+        .find_map(|ext| {
+            let index = name.len().checked_sub(ext.len())?;
+            let matches = lower.len() == name.len()
+                && index > 0
+                && name.is_char_boundary(index)
+                && name[index..].eq_ignore_ascii_case(&ext);
+            matches.then(|| name.split_at(index))
+        });
+    if let Some(split) = compound {
+        return split;
+    }
+    match name.rfind('.') {
+        Some(index) if index > 0 => name.split_at(index),
+        _ => (name, ""),
+    }
+}
+
+impl Config {
+    /// 既知の複合拡張子（小文字）
+    ///
+    /// 組み込みのカタログと別名に、設定のカテゴリと別名で使われているものを加えます
+    pub fn compound_extensions(&self) -> Vec<String> {
+        let mut compounds: Vec<String> = catalogue::all_extensions()
+            .into_iter()
+            .chain(
+                BUILTIN_ALIASES
+                    .iter()
+                    .flat_map(|(alias, target)| [alias.to_string(), target.to_string()]),
+            )
+            .chain(self.categories.values().flatten().cloned())
+            .chain(
+                self.extension_aliases
+                    .iter()
+                    .flat_map(|(alias, target)| [alias.clone(), target.clone()]),
+            )
+            .map(|ext| canonical_form(&ext))
+            .filter(|ext| ext.matches('.').count() > 1)
+            .collect();
+        compounds.sort();
+        compounds.dedup();
+        compounds
+    }
+}
+
+/// 最も短い拡張子（`archive.tar.gz`の場合は`.gz`、拡張子がなければ空文字列）
+pub fn last(file_name: &str) -> String {
+    candidates(file_name).pop().unwrap_or_default()
+}
+
+/// バックアップの拡張子を取り除いたファイル名
+///
+/// `photo.JPG.bak`や`notes.txt~`の場合は元のファイル名を返します
+pub fn strip_backup_suffix(file_name: &str) -> Option<&str> {
+    if let Some(original) = file_name.strip_suffix('~') {
+        return Some(original);
+    }
+    let lower = file_name.to_lowercase();
+    BACKUP_SUFFIXES
+        .iter()
+        .find(|suffix| lower.ends_with(*suffix) && lower.len() > suffix.len())
+        .map(|suffix| &file_name[..file_name.len() - suffix.len()])
+}
+
+/// 代表的な拡張子にそろえる（小文字、先頭の`.`を補う）
+///
+/// 設定の別名を組み込みの別名より優先します
+pub fn canonical(ext: &str, aliases: &HashMap<String, String>) -> String {
+    let ext = canonical_form(ext);
+
+    if let Some(target) = aliases
+        .iter()
+        .find(|(alias, _)| alias.to_lowercase() == ext)
+        .map(|(_, target)| target.to_lowercase())
+    {
+        return target;
+    }
+    BUILTIN_ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == ext)
+        .map(|(_, target)| target.to_string())
+        .unwrap_or(ext)
+}
+
+/// 小文字にして先頭の`.`を補う
+fn canonical_form(ext: &str) -> String {
+    let trimmed = ext.trim().to_lowercase();
+    if trimmed.is_empty() || trimmed.starts_with('.') {
+        trimmed
+    } else {
+        format!(".{}", trimmed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn compounds() -> Vec<String> {
+        Config::default().compound_extensions()
+    }
+
+    #[test]
+    fn split_uses_last_dot_for_ordinary_dotted_names() {
+        let compounds = compounds();
+        assert_eq!(
+            split("John.Smith.CV.pdf", &compounds),
+            ("John.Smith.CV", ".pdf")
+        );
+        assert_eq!(
+            split("Meeting.Notes.Q3.PDF", &compounds),
+            ("Meeting.Notes.Q3", ".PDF")
+        );
+        assert_eq!(
+            split("photo 2024.05.01.jpg", &compounds),
+            ("photo 2024.05.01", ".jpg")
+        );
+    }
+
+    #[test]
+    fn split_keeps_known_compound_extensions() {
+        let compounds = compounds();
+        assert_eq!(split("archive.tar.gz", &compounds), ("archive", ".tar.gz"));
+        assert_eq!(split("Backup.TAR.GZ", &compounds), ("Backup", ".TAR.GZ"));
+        assert_eq!(split("data.gz", &compounds), ("data", ".gz"));
+    }
+
+    #[test]
+    fn split_uses_compounds_from_config() {
+        let mut config = Config::default();
+        config
+            .categories
+            .insert("Scans".to_string(), vec![".scan.pdf".to_string()]);
+        let compounds = config.compound_extensions();
+        assert_eq!(split("doc.scan.pdf", &compounds), ("doc", ".scan.pdf"));
+        assert_eq!(split("doc.scan.pdf", &[]), ("doc.scan", ".pdf"));
+    }
+
+    #[test]
+    fn split_treats_dotfiles_as_having_no_extension() {
+        let compounds = compounds();
+        assert_eq!(split(".bashrc", &compounds), (".bashrc", ""));
+        assert_eq!(split(".bashrc.bak", &compounds), (".bashrc", ".bak"));
+        assert_eq!(split("README", &compounds), ("README", ""));
+    }
+
+    #[test]
+    fn candidates_are_longest_first() {
+        assert_eq!(candidates("Archive.TAR.GZ"), vec![".tar.gz", ".gz"]);
+        assert!(candidates("README").is_empty());
+        assert_eq!(last("archive.tar.gz"), ".gz");
+    }
+
+    #[test]
+    fn canonical_resolves_aliases() {
+        let mut aliases = HashMap::new();
+        assert_eq!(canonical("JPEG", &aliases), ".jpg");
+        assert_eq!(canonical(".tgz", &aliases), ".tar.gz");
+        assert_eq!(canonical(".PNG", &aliases), ".png");
+        aliases.insert(".jpeg".to_string(), ".jpeg".to_string());
+        assert_eq!(canonical(".jpeg", &aliases), ".jpeg");
+    }
+
+    #[test]
+    fn strip_backup_suffix_returns_original_name() {
+        assert_eq!(strip_backup_suffix("photo.JPG.bak"), Some("photo.JPG"));
+        assert_eq!(strip_backup_suffix("notes.txt~"), Some("notes.txt"));
+        assert_eq!(strip_backup_suffix(".bak"), None);
+        assert_eq!(strip_backup_suffix("photo.jpg"), None);
+    }
+}
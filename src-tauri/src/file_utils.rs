@@ -9,24 +9,18 @@ pub fn fix_file_permissions(file_path: &Path) -> io::Result<()> {
     #[cfg(target_os = "macos")]
     {
         // ファイル自体に書き込み権限を付与
-        Command::new("chmod")
-            .arg("u+w")
-            .arg(file_path.to_str().unwrap())
-            .output()?;
+        Command::new("chmod").arg("u+w").arg(file_path).output()?;
 
         // 親ディレクトリに書き込み権限を付与
         if let Some(parent) = file_path.parent() {
-            Command::new("chmod")
-                .arg("u+w")
-                .arg(parent.to_str().unwrap())
-                .output()?;
+            Command::new("chmod").arg("u+w").arg(parent).output()?;
         }
 
         // 拡張属性を削除（オプション）
         Command::new("xattr")
             .arg("-d")
             .arg("com.apple.provenance")
-            .arg(file_path.to_str().unwrap())
+            .arg(file_path)
             .output()
             .ok(); // エラーは無視（属性がない場合もあるため）
     }
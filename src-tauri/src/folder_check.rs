@@ -0,0 +1,210 @@
+// 保存済みフォルダの検証
+//
+// 設定に保存されている入力フォルダ・出力フォルダが現在も使えるかを確認します。
+// 存在しないフォルダ、接続されていないドライブ上のフォルダ、互いに入れ子になっている
+// フォルダ（出力先の中に別の出力先がある、入力フォルダの中に出力先があるなど）を検出します。
+
+use serde::Serialize;
+use std::path::{Component, Path, PathBuf};
+
+use crate::Config;
+
+/// フォルダの問題の種類
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FolderIssueKind {
+    /// フォルダが存在しない
+    Missing,
+    /// フォルダのあるドライブが接続されていない
+    Unmounted,
+    /// パスがフォルダではない
+    NotDirectory,
+    /// 出力先が別の出力先の中にある
+    NestedInOutput,
+    /// 出力先が入力フォルダの中にある
+    NestedInInput,
+    /// 入力フォルダが出力先の中にある
+    InputInOutput,
+}
+
+/// 保存済みフォルダの問題
+#[derive(Serialize, Clone, Debug)]
+pub struct FolderIssue {
+    /// 問題のあるフォルダ
+    pub path: String,
+    /// 問題の種類
+    pub kind: FolderIssueKind,
+    /// 表示用のメッセージ
+    pub message: String,
+}
+
+impl FolderIssue {
+    fn new(path: &str, kind: FolderIssueKind, message: String) -> Self {
+        Self {
+            path: path.to_string(),
+            kind,
+            message,
+        }
+    }
+}
+
+/// 設定に保存されているフォルダを検証
+pub fn check_folders(config: &Config) -> Vec<FolderIssue> {
+    let mut inputs: Vec<&String> = Vec::new();
+    for folder in config
+        .input_folder
+        .iter()
+        .chain(config.input_sources.iter().map(|source| &source.path))
+    {
+        if !inputs.contains(&folder) {
+            inputs.push(folder);
+        }
+    }
+    let mut outputs: Vec<&String> = Vec::new();
+    for folder in config
+        .output_folders
+        .iter()
+        .chain(
+            config
+                .input_sources
+                .iter()
+                .filter_map(|source| source.output_folder.as_ref()),
+        )
+        .chain(config.category_outputs.values())
+    {
+        if !outputs.contains(&folder) {
+            outputs.push(folder);
+        }
+    }
+
+    let mut issues = Vec::new();
+    let mut checked: Vec<&String> = Vec::new();
+
+    // 存在の確認
+    for folder in inputs.iter().chain(&outputs) {
+        if checked.contains(folder) {
+            continue;
+        }
+        checked.push(folder);
+        if let Some(issue) = check_exists(folder) {
+            issues.push(issue);
+        }
+    }
+
+    // 入れ子の確認
+    for output in &outputs {
+        let output_path = normalize(output);
+        for other in &outputs {
+            if output != other && is_nested(&output_path, &normalize(other)) {
+                issues.push(FolderIssue::new(
+                    output,
+                    FolderIssueKind::NestedInOutput,
+                    format!("出力先フォルダが別の出力先 {} の中にあります", other),
+                ));
+            }
+        }
+        for input in &inputs {
+            let input_path = normalize(input);
+            if is_nested(&output_path, &input_path) {
+                issues.push(FolderIssue::new(
+                    output,
+                    FolderIssueKind::NestedInInput,
+                    format!("出力先フォルダが入力フォルダ {} の中にあります", input),
+                ));
+            } else if is_nested(&input_path, &output_path) {
+                issues.push(FolderIssue::new(
+                    input,
+                    FolderIssueKind::InputInOutput,
+                    format!("入力フォルダが出力先フォルダ {} の中にあります", output),
+                ));
+            }
+        }
+    }
+
+    issues
+}
+
+/// フォルダの存在を確認
+fn check_exists(folder: &str) -> Option<FolderIssue> {
+    let path = Path::new(folder);
+    if path.is_dir() {
+        return None;
+    }
+    if path.exists() {
+        return Some(FolderIssue::new(
+            folder,
+            FolderIssueKind::NotDirectory,
+            "フォルダではありません".to_string(),
+        ));
+    }
+    match volume_root(path) {
+        Some(volume) if !volume.exists() => Some(FolderIssue::new(
+            folder,
+            FolderIssueKind::Unmounted,
+            format!("ドライブ {} が接続されていません", volume.display()),
+        )),
+        _ => Some(FolderIssue::new(
+            folder,
+            FolderIssueKind::Missing,
+            "フォルダが存在しません".to_string(),
+        )),
+    }
+}
+
+/// 取り外し可能なドライブのマウント先を取得
+///
+/// macOSの`/Volumes/名前`、Linuxの`/media/ユーザー/名前`・`/run/media/ユーザー/名前`・
+/// `/mnt/名前`、Windowsのドライブ（`E:\`など）を対象とします
+#[cfg(not(windows))]
+fn volume_root(path: &Path) -> Option<PathBuf> {
+    let names: Vec<&std::ffi::OsStr> = path
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(name) => Some(name),
+            _ => None,
+        })
+        .collect();
+    if !path.has_root() {
+        return None;
+    }
+    let depth = match names.first()?.to_str()? {
+        "Volumes" | "mnt" => 2,
+        "media" => 3,
+        "run" if names.get(1).and_then(|name| name.to_str()) == Some("media") => 4,
+        _ => return None,
+    };
+    if names.len() < depth {
+        return None;
+    }
+    let mut root = PathBuf::from("/");
+    root.extend(&names[..depth]);
+    Some(root)
+}
+
+/// 取り外し可能なドライブのマウント先を取得（ドライブのルート）
+#[cfg(windows)]
+fn volume_root(path: &Path) -> Option<PathBuf> {
+    match path.components().next()? {
+        Component::Prefix(prefix) => {
+            let mut root = PathBuf::from(prefix.as_os_str());
+            root.push("\\");
+            Some(root)
+        }
+        _ => None,
+    }
+}
+
+/// 比較用にパスを正規化（存在する場合は実体のパス）
+fn normalize(folder: &str) -> PathBuf {
+    let path = Path::new(folder);
+    path.canonicalize().unwrap_or_else(|_| {
+        path.components()
+            .filter(|component| !matches!(component, Component::CurDir))
+            .collect()
+    })
+}
+
+/// `inner`が`outer`の中（同じフォルダを除く）にあるかどうか
+fn is_nested(inner: &Path, outer: &Path) -> bool {
+    inner != outer && inner.starts_with(outer)
+}
@@ -0,0 +1,128 @@
+// フロントエンドとの間でやり取りするパス
+//
+// UTF-8として解釈できないファイル名（古いWindows環境で作られたShift_JISの名前など）を
+// 失わずにやり取りするためのパス表現です。UTF-8のパスは通常の文字列として、
+// それ以外のパスは表示用の文字列と元のバイト列（Base64）の組としてシリアライズします。
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+/// フロントエンドとやり取りするパス
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct IpcPath(PathBuf);
+
+/// シリアライズ時の表現
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum Repr {
+    /// UTF-8として解釈できるパス
+    Text(String),
+    /// UTF-8として解釈できないパス
+    Raw {
+        /// 表示用に置換文字で補ったパス
+        #[serde(default)]
+        lossy: String,
+        /// 元のパスのバイト列（Base64）
+        raw: String,
+    },
+}
+
+impl IpcPath {
+    /// パスへの参照を取得
+    pub fn as_path(&self) -> &Path {
+        &self.0
+    }
+
+    /// UTF-8として解釈できないパスかどうか
+    pub fn is_undecodable(&self) -> bool {
+        self.0.to_str().is_none()
+    }
+
+    /// 表示用の文字列（解釈できない部分は置換文字になる）
+    pub fn display_lossy(&self) -> String {
+        self.0.to_string_lossy().into_owned()
+    }
+}
+
+impl From<PathBuf> for IpcPath {
+    fn from(path: PathBuf) -> Self {
+        Self(path)
+    }
+}
+
+impl From<&Path> for IpcPath {
+    fn from(path: &Path) -> Self {
+        Self(path.to_path_buf())
+    }
+}
+
+impl AsRef<Path> for IpcPath {
+    fn as_ref(&self) -> &Path {
+        &self.0
+    }
+}
+
+/// OS固有のパス表現をバイト列に変換
+#[cfg(unix)]
+fn os_to_bytes(os: &std::ffi::OsStr) -> Vec<u8> {
+    use std::os::unix::ffi::OsStrExt;
+    os.as_bytes().to_vec()
+}
+
+/// OS固有のパス表現をバイト列に変換（UTF-16のリトルエンディアン）
+#[cfg(windows)]
+fn os_to_bytes(os: &std::ffi::OsStr) -> Vec<u8> {
+    use std::os::windows::ffi::OsStrExt;
+    os.encode_wide().flat_map(u16::to_le_bytes).collect()
+}
+
+/// バイト列をOS固有のパス表現に戻す
+#[cfg(unix)]
+fn bytes_to_os(bytes: Vec<u8>) -> Option<OsString> {
+    use std::os::unix::ffi::OsStringExt;
+    Some(OsString::from_vec(bytes))
+}
+
+/// バイト列をOS固有のパス表現に戻す（UTF-16のリトルエンディアン）
+#[cfg(windows)]
+fn bytes_to_os(bytes: Vec<u8>) -> Option<OsString> {
+    use std::os::windows::ffi::OsStringExt;
+    if bytes.len() % 2 != 0 {
+        return None;
+    }
+    let wide: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+        .collect();
+    Some(OsString::from_wide(&wide))
+}
+
+impl Serialize for IpcPath {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let repr = match self.0.to_str() {
+            Some(text) => Repr::Text(text.to_string()),
+            None => Repr::Raw {
+                lossy: self.display_lossy(),
+                raw: BASE64.encode(os_to_bytes(self.0.as_os_str())),
+            },
+        };
+        repr.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for IpcPath {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match Repr::deserialize(deserializer)? {
+            Repr::Text(text) => Ok(Self(PathBuf::from(text))),
+            Repr::Raw { raw, .. } => {
+                let bytes = BASE64.decode(raw).map_err(serde::de::Error::custom)?;
+                let os = bytes_to_os(bytes)
+                    .ok_or_else(|| serde::de::Error::custom("パスのバイト列が不正です"))?;
+                Ok(Self(PathBuf::from(os)))
+            }
+        }
+    }
+}
@@ -0,0 +1,115 @@
+// 整理ジョブの結果管理
+//
+// 非同期で実行した整理ジョブの結果を保持し、完了後にジョブIDで取得できるようにします。
+// 保持するジョブ数には上限があり、古いものから破棄されます。
+
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::{FileOutcome, FileResult};
+
+/// 結果を保持するジョブ数の上限
+const MAX_STORED_JOBS: usize = 10;
+
+/// ジョブIDの連番
+static JOB_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// 完了したジョブの結果（古い順）
+static JOB_RESULTS: Mutex<VecDeque<(String, Vec<FileResult>)>> = Mutex::new(VecDeque::new());
+
+/// 整理ジョブの集計結果
+#[derive(Serialize, Clone, Debug)]
+pub struct OrganizeSummary {
+    /// ジョブID
+    pub job_id: String,
+    /// 処理対象の総ファイル数（スキップを含む）
+    pub total_files: usize,
+    /// 移動に成功したファイル数
+    pub succeeded: usize,
+    /// 移動に失敗したファイル数
+    pub failed: usize,
+    /// スキップしたファイル数
+    pub skipped: usize,
+    /// コピーはできたが元ファイルを削除できなかったファイル数（移動の成功には数えない）
+    pub source_retained: usize,
+    /// カテゴリごとの移動ファイル数
+    pub per_category: HashMap<String, usize>,
+    /// 処理にかかった時間（ミリ秒）
+    pub elapsed_ms: u64,
+    /// 途中で中断されたかどうか
+    pub cancelled: bool,
+    /// 移動先の容量不足で途中で止めたかどうか
+    pub disk_full: bool,
+}
+
+impl OrganizeSummary {
+    /// 処理結果から集計を作成
+    pub fn from_results(
+        job_id: &str,
+        total_files: usize,
+        results: &[FileResult],
+        elapsed_ms: u64,
+        cancelled: bool,
+        disk_full: bool,
+    ) -> Self {
+        let mut summary = Self {
+            job_id: job_id.to_string(),
+            total_files,
+            succeeded: 0,
+            failed: 0,
+            skipped: 0,
+            source_retained: 0,
+            per_category: HashMap::new(),
+            elapsed_ms,
+            cancelled,
+            disk_full,
+        };
+
+        for result in results {
+            match result.outcome {
+                FileOutcome::Moved => {
+                    summary.succeeded += 1;
+                    if let Some(category) = &result.category {
+                        *summary.per_category.entry(category.clone()).or_insert(0) += 1;
+                    }
+                }
+                FileOutcome::Failed => summary.failed += 1,
+                FileOutcome::Skipped => summary.skipped += 1,
+                FileOutcome::SourceRetained => summary.source_retained += 1,
+            }
+        }
+
+        summary
+    }
+}
+
+/// 新しいジョブIDを発行
+pub fn new_job_id() -> String {
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or(0);
+    let seq = JOB_COUNTER.fetch_add(1, Ordering::SeqCst);
+    format!("job-{}-{}", millis, seq)
+}
+
+/// ジョブの結果を保存
+pub fn store_results(job_id: &str, results: Vec<FileResult>) {
+    let mut jobs = JOB_RESULTS.lock().unwrap_or_else(|e| e.into_inner());
+    jobs.retain(|(id, _)| id != job_id);
+    jobs.push_back((job_id.to_string(), results));
+    while jobs.len() > MAX_STORED_JOBS {
+        jobs.pop_front();
+    }
+}
+
+/// ジョブの結果を取得
+pub fn get_results(job_id: &str) -> Option<Vec<FileResult>> {
+    let jobs = JOB_RESULTS.lock().unwrap_or_else(|e| e.into_inner());
+    jobs.iter()
+        .find(|(id, _)| id == job_id)
+        .map(|(_, results)| results.clone())
+}
@@ -3,31 +3,74 @@
 // このライブラリは、ファイルをカテゴリごとに分類・整理するための機能を提供します。
 // 設定されたルールに基づいてファイルの拡張子を認識し、適切なフォルダに移動します。
 
+mod catalogue;
+mod classify;
+mod collision;
+mod config_file;
+mod config_watch;
+mod extensions;
+mod folder_check;
+mod ipc_path;
+mod jobs;
+mod migration;
+mod profiles;
+mod progress;
+mod rename;
+mod retry;
+mod routing;
+mod rule_sets;
+mod sanitize;
+mod scan;
+mod scan_job;
+mod sources;
+mod space;
+mod suggestions;
+
+use catalogue::{CatalogueMergeResult, DisplayName};
+use classify::{FallbackOptions, Subcategory};
+use collision::{CollisionOptions, NameIndex};
+use folder_check::FolderIssue;
+use ipc_path::IpcPath;
+use jobs::OrganizeSummary;
+use profiles::ProfileList;
+use progress::ProgressReporter;
+use rename::RenameRule;
+use retry::ErrorClass;
+use rule_sets::{ImportMode, RuleConflict, RuleSet, SharedRuleSet};
+use sanitize::{FileSystem, SanitizeOptions};
+use scan::{CollectSink, ScanOptions, ScanSink, ScanWarning, Scanner};
 use serde::{Deserialize, Serialize};
+use sources::InputSource;
+use space::SpaceCheck;
 use std::collections::HashMap;
+use std::ffi::{OsStr, OsString};
 use std::fs::{self, File};
-use std::io::{Read, Write};
+use std::io::Read;
 #[cfg(unix)]
 use std::os::unix::fs::PermissionsExt;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::thread;
-use std::time::Duration;
+use std::time::Instant;
+use suggestions::Suggestion;
 use tauri::Emitter;
 use tokio::fs as tokio_fs;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
 
 // -----------------------------------------------------------------------------
 // 定数定義
 // -----------------------------------------------------------------------------
 
-/// デフォルトカテゴリ名
+/// 未分類フォルダの既定の名前
 const DEFAULT_CATEGORY: &str = "Others";
 
-/// 一度に処理するファイルの数
-const BATCH_SIZE: usize = 10;
+/// コピー進捗を通知する対象とするファイルサイズの下限（バイト）
+const LARGE_FILE_THRESHOLD: u64 = 64 * 1024 * 1024;
 
-/// バッチ間の待機時間（ミリ秒）
-const BATCH_DELAY_MS: u64 = 50;
+/// 進捗付きコピーで一度に読み書きするサイズ（バイト）
+const COPY_CHUNK_SIZE: usize = 1024 * 1024;
+
+/// ファイル移動の最大試行回数（一時的なエラーの場合のみ再試行する）
+const MAX_MOVE_ATTEMPTS: u8 = 3;
 
 // -----------------------------------------------------------------------------
 // グローバル状態
@@ -92,118 +135,252 @@ fn to_string_error<T>(result: Result<T>) -> std::result::Result<T, String> {
 // -----------------------------------------------------------------------------
 
 /// アプリケーション設定
+///
+/// 設定ファイルにない項目は既定値で補います
 #[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(default)]
 struct Config {
+    /// 設定ファイルの形式のバージョン
+    version: u32,
     /// ファイル拡張子とカテゴリのマッピング
     categories: HashMap<String, Vec<String>>,
+    /// 親カテゴリごとのサブカテゴリのルール
+    subcategories: HashMap<String, Vec<Subcategory>>,
+    /// どのカテゴリにも当てはまらないファイルの扱い
+    fallback: FallbackOptions,
+    /// 拡張子の別名（別名から代表的な拡張子へのマッピング）
+    extension_aliases: HashMap<String, String>,
+    /// カテゴリごとのファイル名の変更ルール（適用する順に並べる）
+    rename_rules: HashMap<String, Vec<RenameRule>>,
+    /// 移動先のファイル名の衝突の判定
+    collisions: CollisionOptions,
+    /// 移動先のファイルシステムで使えない名前の扱い
+    sanitize: SanitizeOptions,
+    /// コピー後に元ファイルを削除できなかった場合にコピーを取り消すか
+    #[serde(default)]
+    rollback_retained_copies: bool,
     /// 出力先フォルダのリスト
     output_folders: Vec<String>,
     /// 入力フォルダ設定
     input_folder: Option<String>,
+    /// 入力元フォルダごとの設定
+    #[serde(default)]
+    input_sources: Vec<InputSource>,
+    /// カテゴリごとの出力先フォルダ（未設定のカテゴリは既定の出力先）
+    #[serde(default)]
+    category_outputs: HashMap<String, String>,
+    /// スキャン時の除外設定
+    #[serde(default)]
+    scan: ScanOptions,
+    /// 共有のルールセットを置くフォルダ
+    shared_rules_dir: Option<String>,
+    /// カテゴリの表示名（フォルダ名とは別に画面で表示する名前）
+    display_names: HashMap<String, DisplayName>,
+    /// 取り込み済みのカタログの拡張子（ユーザーが削除したものを再び追加しないため）
+    #[serde(default)]
+    catalogue_extensions: Vec<String>,
 }
 
 impl Default for Config {
     fn default() -> Self {
-        let mut categories = HashMap::new();
-        categories.insert(
-            "Images".to_string(),
-            vec![
-                ".jpg".to_string(),
-                ".jpeg".to_string(),
-                ".png".to_string(),
-                ".gif".to_string(),
-                ".bmp".to_string(),
-            ],
-        );
-        categories.insert(
-            "Documents".to_string(),
-            vec![
-                ".pdf".to_string(),
-                ".doc".to_string(),
-                ".docx".to_string(),
-                ".txt".to_string(),
-                ".xlsx".to_string(),
-                ".pptx".to_string(),
-            ],
-        );
-        categories.insert(
-            "Videos".to_string(),
-            vec![
-                ".mp4".to_string(),
-                ".avi".to_string(),
-                ".mov".to_string(),
-                ".wmv".to_string(),
-                ".mkv".to_string(),
-            ],
-        );
-        categories.insert(
-            "Audio".to_string(),
-            vec![
-                ".mp3".to_string(),
-                ".wav".to_string(),
-                ".ogg".to_string(),
-                ".flac".to_string(),
-                ".aac".to_string(),
-            ],
-        );
-        categories.insert(
-            "Archives".to_string(),
-            vec![
-                ".zip".to_string(),
-                ".rar".to_string(),
-                ".7z".to_string(),
-                ".tar".to_string(),
-                ".gz".to_string(),
-            ],
-        );
-
         Config {
-            categories,
+            version: migration::CURRENT_CONFIG_VERSION,
+            categories: catalogue::default_categories(),
+            subcategories: HashMap::new(),
+            fallback: FallbackOptions::default(),
+            extension_aliases: HashMap::new(),
+            rename_rules: HashMap::new(),
+            collisions: CollisionOptions::default(),
+            sanitize: SanitizeOptions::default(),
+            rollback_retained_copies: false,
             output_folders: vec![],
             input_folder: None,
+            input_sources: vec![],
+            category_outputs: HashMap::new(),
+            scan: ScanOptions::default(),
+            shared_rules_dir: None,
+            display_names: catalogue::default_display_names(),
+            catalogue_extensions: catalogue::all_extensions(),
         }
     }
 }
 
 /// 設定ファイルのパスを取得
-fn get_config_path() -> PathBuf {
-    dirs::home_dir()
-        .expect("Failed to get home directory")
-        .join(".shiwakekunrc.json")
+fn get_config_path() -> Result<PathBuf> {
+    profiles::config_path()
 }
 
-/// 設定を読み込む
-#[tauri::command]
-fn load_config() -> std::result::Result<Config, String> {
-    let config_path = get_config_path();
+/// 読み込んだ設定と検証結果
+#[derive(Serialize, Clone, Debug)]
+struct LoadedConfig {
+    /// 設定
+    config: Config,
+    /// 保存済みフォルダの問題
+    folder_issues: Vec<FolderIssue>,
+    /// 読み込み時の注意事項（古い形式からの移行など）
+    warnings: Vec<String>,
+}
+
+/// 設定ファイルを読み込む
+///
+/// 設定ファイルが壊れている場合は、読み込めた最新のバックアップから復元します。
+/// 古い形式の設定ファイルは、バックアップを作成したうえで現在の形式に移行して保存します。
+/// ホームフォルダにある以前の設定ファイルは、設定フォルダに移動してから読み込みます。
+/// 注意事項があれば`warnings`に追加します
+fn read_config(warnings: &mut Vec<String>) -> Result<Config> {
+    if let Some(new_path) = profiles::migrate_legacy_config()? {
+        warnings.push(format!(
+            "設定ファイルを {} に移動しました",
+            new_path.display()
+        ));
+    }
+
+    let config_path = get_config_path()?;
     if !config_path.exists() {
         return Ok(Config::default());
     }
 
-    let result: Result<Config> = (|| {
-        let mut file = File::open(&config_path)?;
-        let mut contents = String::new();
-        file.read_to_string(&mut contents)?;
-        Ok(serde_json::from_str(&contents)?)
-    })();
+    let (config, from_version) = match parse_config_file(&config_path) {
+        Ok(parsed) => parsed,
+        // 読み込めない・解析できない場合のみバックアップから復元する
+        // （新しいバージョンの設定などは上書きしないようにそのままエラーにする）
+        Err(e @ (AppError::Io(_) | AppError::Json(_))) => {
+            let (backup_path, restored) = config_file::backups(&config_path)
+                .into_iter()
+                .find_map(|backup| {
+                    parse_config_file(&backup)
+                        .ok()
+                        .filter(|(config, _)| config.validate().is_ok())
+                        .map(|(config, _)| (backup, config))
+                })
+                .ok_or(e)?;
+            let corrupt_path = config_file::keep_corrupt(&config_path)?;
+            write_config(&restored)?;
+            warnings.push(format!(
+                "設定ファイルを読み込めなかったため、バックアップ {} から復元しました（読み込めなかった設定: {}）",
+                backup_path.display(),
+                corrupt_path.display()
+            ));
+            return Ok(restored);
+        }
+        Err(e) => return Err(e),
+    };
+    config
+        .validate()
+        .map_err(|e| AppError::Custom(format!("設定ファイルの内容が不正です: {}", e)))?;
+
+    if from_version != migration::CURRENT_CONFIG_VERSION {
+        let backup_path = migration::backup_before_migration(&config_path, from_version)?;
+        write_config(&config)?;
+        warnings.push(format!(
+            "設定ファイルをバージョン{}から{}に移行しました（移行前の設定: {}）",
+            from_version,
+            migration::CURRENT_CONFIG_VERSION,
+            backup_path.display()
+        ));
+    }
 
-    to_string_error(result)
+    Ok(config)
+}
+
+impl Config {
+    /// 設定の内容を確認（保存・読み込み時に、出力先の外に出る名前などを拒否する）
+    fn validate(&self) -> std::result::Result<(), String> {
+        self.fallback.validate()?;
+        for (category, subcategories) in &self.subcategories {
+            for subcategory in subcategories {
+                subcategory
+                    .validate()
+                    .map_err(|e| format!("{}のサブカテゴリ: {}", category, e))?;
+            }
+        }
+        self.validate_rename_rules()
+    }
+}
+
+/// 設定ファイルを解析し、現在の形式に移行した設定と移行元のバージョンを返す
+fn parse_config_file(path: &Path) -> Result<(Config, u32)> {
+    let mut file = File::open(path)?;
+    let mut contents = String::new();
+    file.read_to_string(&mut contents)?;
+
+    let mut value: serde_json::Value = serde_json::from_str(&contents)?;
+    let from_version = migration::migrate(&mut value)?;
+    Ok((serde_json::from_value(value)?, from_version))
+}
+
+/// 設定ファイルに書き込む
+///
+/// 直前の設定をバックアップしてから、一時ファイル経由で置き換えます
+fn write_config(config: &Config) -> Result<()> {
+    let config_path = get_config_path()?;
+    let serialized = serde_json::to_string_pretty(config)?;
+    if let Some(parent) = config_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    if config_path.exists() {
+        config_file::rotate_backups(&config_path)?;
+    }
+    config_file::write_atomic(&config_path, serialized.as_bytes())?;
+    config_watch::remember(&config_path);
+    Ok(())
 }
 
-/// 設定を保存する
+/// 設定を読み込む
+///
+/// 保存済みのフォルダが存在しない・入れ子になっているなどの問題も合わせて返します
 #[tauri::command]
-fn save_config(config: Config) -> std::result::Result<(), String> {
-    let result: Result<()> = (|| {
-        let config_path = get_config_path();
-        let serialized = serde_json::to_string_pretty(&config)?;
-        let mut file = File::create(&config_path)?;
-        file.write_all(serialized.as_bytes())?;
-        Ok(())
+fn load_config() -> std::result::Result<LoadedConfig, String> {
+    let result: Result<LoadedConfig> = (|| {
+        let mut warnings = Vec::new();
+        let config = read_config(&mut warnings)?;
+        config_watch::remember(&get_config_path()?);
+        let folder_issues = folder_check::check_folders(&config);
+        Ok(LoadedConfig {
+            config,
+            folder_issues,
+            warnings,
+        })
     })();
 
     to_string_error(result)
 }
 
+/// プロファイルの一覧を取得
+#[tauri::command]
+fn list_profiles() -> std::result::Result<ProfileList, String> {
+    to_string_error(profiles::list_profiles())
+}
+
+/// プロファイルを切り替えて設定を読み込む（`name`が未指定の場合は既定のプロファイル）
+///
+/// まだ設定ファイルのないプロファイルは既定の設定で始まります
+#[tauri::command]
+fn switch_profile(name: Option<String>) -> std::result::Result<LoadedConfig, String> {
+    to_string_error(profiles::switch_profile(name))?;
+    load_config()
+}
+
+/// プロファイルを削除
+#[tauri::command]
+fn delete_profile(name: String) -> std::result::Result<(), String> {
+    to_string_error(profiles::delete_profile(&name))
+}
+
+/// 保存済みのフォルダを検証
+#[tauri::command]
+fn check_config_folders(config: Config) -> Vec<FolderIssue> {
+    folder_check::check_folders(&config)
+}
+
+/// 設定を保存する（常に現在の形式のバージョンで保存）
+#[tauri::command]
+fn save_config(mut config: Config) -> std::result::Result<(), String> {
+    config.validate()?;
+    config.version = migration::CURRENT_CONFIG_VERSION;
+    to_string_error(write_config(&config))
+}
+
 /// 設定を更新して保存する
 fn update_and_save_config<F>(config: Config, update_fn: F) -> std::result::Result<Config, String>
 where
@@ -225,6 +402,34 @@ fn add_output_folder(folder: String, config: Config) -> std::result::Result<Conf
     })
 }
 
+/// 出力フォルダを削除
+#[tauri::command]
+fn remove_output_folder(folder: String, config: Config) -> std::result::Result<Config, String> {
+    update_and_save_config(config, |new_config| {
+        new_config
+            .output_folders
+            .retain(|existing| existing != &folder);
+    })
+}
+
+/// 出力フォルダの並び順を変更（`index`は移動先の位置）
+#[tauri::command]
+fn move_output_folder(
+    folder: String,
+    index: usize,
+    config: Config,
+) -> std::result::Result<Config, String> {
+    let Some(current) = config.output_folders.iter().position(|f| f == &folder) else {
+        return Err(format!("出力フォルダが見つかりません: {}", folder));
+    };
+
+    update_and_save_config(config, |new_config| {
+        let folder = new_config.output_folders.remove(current);
+        let index = index.min(new_config.output_folders.len());
+        new_config.output_folders.insert(index, folder);
+    })
+}
+
 /// 入力フォルダを設定
 #[tauri::command]
 fn set_input_folder(folder: String, config: Config) -> std::result::Result<Config, String> {
@@ -233,155 +438,688 @@ fn set_input_folder(folder: String, config: Config) -> std::result::Result<Confi
     })
 }
 
+/// 入力フォルダの設定を解除
+#[tauri::command]
+fn clear_input_folder(config: Config) -> std::result::Result<Config, String> {
+    update_and_save_config(config, |new_config| {
+        new_config.input_folder = None;
+    })
+}
+
+/// 入力元を追加（同じパスの入力元が既にある場合は置き換える）
+#[tauri::command]
+fn add_input_source(source: InputSource, config: Config) -> std::result::Result<Config, String> {
+    update_and_save_config(config, |new_config| {
+        match new_config
+            .input_sources
+            .iter_mut()
+            .find(|existing| existing.path == source.path)
+        {
+            Some(existing) => *existing = source,
+            None => new_config.input_sources.push(source),
+        }
+    })
+}
+
+/// 入力元を削除
+#[tauri::command]
+fn remove_input_source(path: String, config: Config) -> std::result::Result<Config, String> {
+    update_and_save_config(config, |new_config| {
+        new_config
+            .input_sources
+            .retain(|source| source.path != path);
+        if new_config.input_folder.as_ref() == Some(&path) {
+            new_config.input_folder = None;
+        }
+    })
+}
+
+/// カテゴリの出力先を設定（`folder`が未指定の場合は既定の出力先に戻す）
+#[tauri::command]
+fn set_category_output(
+    category: String,
+    folder: Option<String>,
+    config: Config,
+) -> std::result::Result<Config, String> {
+    update_and_save_config(config, |new_config| match folder {
+        Some(folder) => {
+            new_config.category_outputs.insert(category, folder);
+        }
+        None => {
+            new_config.category_outputs.remove(&category);
+        }
+    })
+}
+
+/// ルールセットの読み込み結果
+#[derive(Serialize, Clone, Debug)]
+struct RuleImportResult {
+    /// 反映後の設定
+    config: Config,
+    /// 既存のルールと競合したため反映しなかったルール
+    conflicts: Vec<RuleConflict>,
+}
+
+/// カテゴリのルールをファイルに書き出す（形式は拡張子で判断）
+#[tauri::command]
+fn export_rule_set(path: IpcPath, config: Config) -> std::result::Result<(), String> {
+    to_string_error(rule_sets::export(
+        &RuleSet::from_config(&config),
+        path.as_path(),
+    ))
+}
+
+/// ファイルからカテゴリのルールを読み込んで設定に反映
+#[tauri::command]
+fn import_rule_set(
+    path: IpcPath,
+    mode: ImportMode,
+    config: Config,
+) -> std::result::Result<RuleImportResult, String> {
+    let rule_set = to_string_error(rule_sets::read(path.as_path()))?;
+    let mut conflicts = Vec::new();
+    let config = update_and_save_config(config, |new_config| {
+        conflicts = rule_sets::apply(new_config, rule_set, mode);
+    })?;
+    Ok(RuleImportResult { config, conflicts })
+}
+
+/// 共有のルールセットを置くフォルダを設定（`folder`が未指定の場合は解除）
+#[tauri::command]
+fn set_shared_rules_dir(
+    folder: Option<String>,
+    config: Config,
+) -> std::result::Result<Config, String> {
+    update_and_save_config(config, |new_config| {
+        new_config.shared_rules_dir = folder;
+    })
+}
+
+/// 共有フォルダにあるルールセットを一覧
+#[tauri::command]
+fn list_shared_rule_sets(config: Config) -> std::result::Result<Vec<SharedRuleSet>, String> {
+    let Some(dir) = config.shared_rules_dir else {
+        return Ok(vec![]);
+    };
+    to_string_error(rule_sets::list_shared(Path::new(&dir)))
+}
+
+/// 当てはまらないファイルの扱いを設定
+#[tauri::command]
+fn set_fallback(fallback: FallbackOptions, config: Config) -> std::result::Result<Config, String> {
+    update_and_save_config(config, |new_config| {
+        new_config.fallback = fallback;
+    })
+}
+
+/// 移動先のファイル名の衝突の判定方法を設定
+#[tauri::command]
+fn set_collision_options(
+    collisions: CollisionOptions,
+    config: Config,
+) -> std::result::Result<Config, String> {
+    update_and_save_config(config, |new_config| {
+        new_config.collisions = collisions;
+    })
+}
+
+/// 移動先のファイルシステムで使えない名前の扱いを設定
+#[tauri::command]
+fn set_sanitize_options(
+    sanitize: SanitizeOptions,
+    config: Config,
+) -> std::result::Result<Config, String> {
+    update_and_save_config(config, |new_config| {
+        new_config.sanitize = sanitize;
+    })
+}
+
+/// コピー後に元ファイルを削除できなかった場合にコピーを取り消すかを設定
+#[tauri::command]
+fn set_rollback_retained_copies(
+    enabled: bool,
+    config: Config,
+) -> std::result::Result<Config, String> {
+    update_and_save_config(config, |new_config| {
+        new_config.rollback_retained_copies = enabled;
+    })
+}
+
+/// 組み込みのカタログに新しく追加された項目を設定に取り込む
+///
+/// ユーザーが編集・削除したカテゴリや拡張子は上書きしません
+#[tauri::command]
+fn merge_catalogue(config: Config) -> std::result::Result<CatalogueMergeResult, String> {
+    let mut added = Vec::new();
+    let config = update_and_save_config(config, |new_config| {
+        added = catalogue::merge_into(new_config);
+    })?;
+    Ok(CatalogueMergeResult { config, added })
+}
+
+/// 確認待ちフォルダにあるファイルを一覧
+#[tauri::command]
+fn list_review_queue(
+    output_folder: String,
+    config: Config,
+) -> std::result::Result<Vec<IpcPath>, String> {
+    let review_dir = Path::new(&output_folder).join(&config.fallback.review_folder_name);
+    if !review_dir.is_dir() {
+        return Ok(vec![]);
+    }
+
+    let result: Result<Vec<IpcPath>> = (|| {
+        let mut files = Vec::new();
+        for entry in fs::read_dir(&review_dir)? {
+            let path = entry?.path();
+            if path.is_file() {
+                files.push(IpcPath::from(path));
+            }
+        }
+        files.sort_by(|a, b| a.as_path().cmp(b.as_path()));
+        Ok(files)
+    })();
+
+    to_string_error(result)
+}
+
+/// 未分類フォルダにあるファイルを分析し、設定の変更を提案
+#[tauri::command]
+fn suggest_categories(
+    output_folder: String,
+    config: Config,
+) -> std::result::Result<Vec<Suggestion>, String> {
+    let output_roots = get_output_roots(&config, Path::new(&output_folder));
+    Ok(suggestions::suggest(&config, &output_roots))
+}
+
+/// 提案の反映結果
+#[derive(Serialize, Clone, Debug)]
+struct SuggestionApplyResult {
+    /// 反映後の設定
+    config: Config,
+    /// 対象のファイルを整理し直した結果（整理し直さない場合は空）
+    results: Vec<FileResult>,
+}
+
+/// 提案を設定に反映し、`reorganize`が指定された場合は対象のファイルを整理し直す
+///
+/// 未分類フォルダのファイルは通常の整理ではスキップされるため、ここで直接振り分けます
+#[tauri::command]
+fn apply_suggestions(
+    suggestions: Vec<Suggestion>,
+    output_folder: String,
+    reorganize: bool,
+    config: Config,
+) -> std::result::Result<SuggestionApplyResult, String> {
+    let config = update_and_save_config(config, |new_config| {
+        for suggestion in &suggestions {
+            suggestions::apply(new_config, suggestion);
+        }
+    })?;
+    if !reorganize {
+        return Ok(SuggestionApplyResult {
+            config,
+            results: vec![],
+        });
+    }
+
+    let result: Result<Vec<FileResult>> = (|| {
+        let output_path = Path::new(&output_folder);
+        let files: Vec<IpcPath> = suggestions
+            .into_iter()
+            .flat_map(|suggestion| suggestion.files)
+            .collect();
+        routing::validate_output_roots(&config, &files, output_path)?;
+
+        // 各ファイルを再整理（容量不足になった場合は残りを処理しない）
+        let mut context = OrganizeContext::default();
+        let mut results = Vec::new();
+        for file_path in &files {
+            let result =
+                process_single_file(file_path, output_path, &config, &mut context, &|_, _| {});
+            results.push(result);
+            if context.disk_full {
+                break;
+            }
+        }
+
+        Ok(results)
+    })();
+
+    Ok(SuggestionApplyResult {
+        results: to_string_error(result)?,
+        config,
+    })
+}
+
 /// カテゴリー名のリストを取得
+///
+/// 入力元ごとのルールで使われるカテゴリ名と、設定に応じた未分類・確認待ちフォルダの
+/// 名前も含みます
 fn get_category_names(config: &Config) -> Vec<String> {
-    config
+    let mut names: Vec<String> = config
         .categories
         .keys()
+        .chain(
+            config
+                .input_sources
+                .iter()
+                .filter_map(|source| source.categories.as_ref())
+                .flat_map(|categories| categories.keys()),
+        )
         .cloned()
-        .chain(std::iter::once(DEFAULT_CATEGORY.to_string())) // デフォルトカテゴリも追加
-        .collect()
+        .chain(config.fallback.folder_names()) // 未分類フォルダも追加
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// 整理先となりうる出力フォルダの一覧を取得
+///
+/// 整理時に指定した出力先と、入力元ごと・カテゴリごとの出力先を含みます
+fn get_output_roots(config: &Config, output_folder: &Path) -> Vec<PathBuf> {
+    let mut roots = vec![output_folder.to_path_buf()];
+    let configured = config
+        .input_sources
+        .iter()
+        .filter_map(|source| source.output_folder.as_ref())
+        .chain(config.category_outputs.values());
+    for folder in configured {
+        let folder = PathBuf::from(folder);
+        if !roots.contains(&folder) {
+            roots.push(folder);
+        }
+    }
+    roots
 }
 
-/// ファイル拡張子からカテゴリを取得
-fn get_category(ext: &str, categories: &HashMap<String, Vec<String>>) -> String {
-    let ext_lower = ext.to_lowercase();
+/// ファイル拡張子からカテゴリを取得（当てはまるカテゴリがなければNone）
+///
+/// 拡張子は別名を代表的な拡張子にそろえてから比較します
+fn get_category(
+    ext: &str,
+    categories: &HashMap<String, Vec<String>>,
+    aliases: &HashMap<String, String>,
+) -> Option<String> {
+    let ext = extensions::canonical(ext, aliases);
     for (category, exts) in categories {
-        if exts.iter().any(|e| e.to_lowercase() == ext_lower) {
-            return category.clone();
+        if exts
+            .iter()
+            .any(|e| extensions::canonical(e, aliases) == ext)
+        {
+            return Some(category.clone());
         }
     }
-    DEFAULT_CATEGORY.to_string()
+    None
+}
+
+/// ファイル処理の結果種別
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+enum FileOutcome {
+    /// カテゴリフォルダへ移動した
+    Moved,
+    /// 移動に失敗した
+    Failed,
+    /// 処理対象外としてスキップした
+    Skipped,
+    /// コピーはできたが元ファイルを削除できず、両方に残っている
+    SourceRetained,
 }
 
 /// ファイル処理結果
 #[derive(Serialize, Deserialize, Clone, Debug)]
 struct FileResult {
     /// 処理されたファイルのパス
-    file_path: String,
+    file_path: IpcPath,
     /// 処理が成功したかどうか
     success: bool,
+    /// 処理結果の種別
+    outcome: FileOutcome,
+    /// 振り分け先のカテゴリ
+    category: Option<String>,
     /// 処理結果のメッセージ
     message: String,
 }
 
 impl FileResult {
     /// 成功結果を作成
-    fn success(file_path: String, message: String) -> Self {
+    fn success(file_path: IpcPath, category: String, message: String) -> Self {
         Self {
             file_path,
             success: true,
+            outcome: FileOutcome::Moved,
+            category: Some(category),
             message,
         }
     }
 
     /// エラー結果を作成
-    fn error(file_path: String, message: String) -> Self {
+    fn error(file_path: IpcPath, message: String) -> Self {
+        Self {
+            file_path,
+            success: false,
+            outcome: FileOutcome::Failed,
+            category: None,
+            message,
+        }
+    }
+
+    /// コピー後に元ファイルを削除できなかった結果を作成
+    fn source_retained(file_path: IpcPath, category: String, message: String) -> Self {
         Self {
             file_path,
             success: false,
+            outcome: FileOutcome::SourceRetained,
+            category: Some(category),
             message,
         }
     }
+
+    /// スキップ結果を作成
+    fn skipped(file_path: IpcPath, message: String) -> Self {
+        Self {
+            file_path,
+            success: true,
+            outcome: FileOutcome::Skipped,
+            category: None,
+            message,
+        }
+    }
+}
+
+/// ファイル移動の失敗
+#[derive(Debug)]
+enum MoveError {
+    /// 移動できなかった（元ファイルはそのまま残っている）
+    Failed(std::io::Error),
+    /// コピーはできたが元ファイルを削除できなかった（両方に残っている）
+    SourceRetained(std::io::Error),
+    /// 元ファイルを削除できなかったため、コピーを取り消した
+    RolledBack(std::io::Error),
+}
+
+impl From<std::io::Error> for MoveError {
+    fn from(err: std::io::Error) -> Self {
+        MoveError::Failed(err)
+    }
 }
 
 /// ファイルを移動する非同期ヘルパー関数
 ///
-/// 複数の方法でファイル移動を試み、可能な限り確実に移動を実行します
-async fn move_file_async(src: &Path, dst: &Path) -> std::io::Result<()> {
-    // 最大リトライ回数
-    const MAX_RETRIES: u8 = 3;
-    let mut last_error = None;
+/// 同じデバイス上では名前の変更で、異なるデバイスへはコピーと削除で移動します。
+/// 一時的なエラー（他のプロセスが使用中など）のみ間隔を空けて再試行し、
+/// 失敗した場合は途中までコピーした移動先のファイルを削除します。
+/// コピー後に元ファイルを削除できない場合は再びコピーせず、`rollback`に応じてコピーを取り消します。
+/// 大きなファイルのコピー中は`on_progress`にコピー済みバイト数と総バイト数を渡します
+async fn move_file_async(
+    src: &Path,
+    dst: &Path,
+    rollback: bool,
+    on_progress: &dyn Fn(u64, u64),
+) -> std::result::Result<(), MoveError> {
+    // シンボリックリンクはリンク先ではなくリンク自体を移動する
+    let is_symlink = tokio_fs::symlink_metadata(src)
+        .await
+        .is_ok_and(|meta| meta.file_type().is_symlink());
+    if is_symlink {
+        return move_symlink(src, dst).await;
+    }
+
+    // 既にあるファイルは片付けの対象にしない
+    let dst_existed = dst.exists();
+
+    let mut attempt = 0;
+    loop {
+        attempt += 1;
+        let err = match try_move(src, dst, on_progress).await {
+            Ok(()) => return Ok(()),
+            Err(MoveError::SourceRetained(err)) => {
+                return retry_source_removal(src, dst, err, attempt, rollback && !dst_existed)
+                    .await;
+            }
+            Err(MoveError::Failed(err) | MoveError::RolledBack(err)) => err,
+        };
 
-    // 複数回試行する
-    for attempt in 0..MAX_RETRIES {
-        if attempt > 0 {
-            // リトライ前に少し待機（待機時間は試行回数に応じて増加）
-            thread::sleep(Duration::from_millis(100 * attempt as u64));
+        // 途中までコピーした移動先のファイルを削除
+        if !dst_existed {
+            remove_partial_copy(src, dst).await;
         }
+        // 容量不足などの再試行しても解消しないエラーはそのまま返す
+        if retry::classify(&err) == ErrorClass::Permanent || attempt >= MAX_MOVE_ATTEMPTS {
+            return Err(err.into());
+        }
+        // 一時的なエラーは少し待ってから再試行（待機時間は試行回数に応じて増加）
+        tokio::time::sleep(retry::backoff(attempt)).await;
+    }
+}
 
-        // 方法1: 非同期コピー&削除を試行
-        if let Ok(()) = try_async_copy_remove(src, dst).await {
-            return Ok(());
+/// シンボリックリンクをリンクのまま移動
+///
+/// 絶対パスのリンクは同じデバイス上では名前を変更し、それ以外は同じリンク先のリンクを
+/// 作り直します。相対パスのリンクは移動すると切れるため、元の場所から見た絶対パスにします
+async fn move_symlink(src: &Path, dst: &Path) -> std::result::Result<(), MoveError> {
+    let target = tokio_fs::read_link(src).await?;
+    if target.is_absolute() {
+        match tokio_fs::rename(src, dst).await {
+            Ok(()) => return Ok(()),
+            Err(e) if e.kind() != std::io::ErrorKind::CrossesDevices => return Err(e.into()),
+            Err(_) => {}
         }
+    }
 
-        // 方法2: 通常のrenameを試行（異なるファイルシステム間でも動作する場合がある）
-        if fs::rename(src, dst).is_ok() {
-            return Ok(());
+    let target = match src.parent() {
+        Some(parent) if target.is_relative() => parent.join(target),
+        _ => target,
+    };
+    #[cfg(unix)]
+    tokio_fs::symlink(&target, dst).await?;
+    #[cfg(windows)]
+    tokio_fs::symlink_file(&target, dst).await?;
+
+    match tokio_fs::remove_file(src).await {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => {
+            // 元のリンクを削除できない場合は作ったリンクを取り消す
+            let _ = tokio_fs::remove_file(dst).await;
+            Err(e.into())
+        }
+    }
+}
+
+/// 途中までコピーした移動先のファイルを削除
+///
+/// 元ファイルが無くなっている場合は、移動先が残っている唯一のデータの可能性があるため削除しません
+async fn remove_partial_copy(src: &Path, dst: &Path) {
+    if tokio_fs::try_exists(src).await.unwrap_or(false) {
+        let _ = tokio_fs::remove_file(dst).await;
+    }
+}
+
+/// コピー後に削除できなかった元ファイルの削除を再試行
+///
+/// 移動先へのコピーは済んでいるため、一時的なエラーの場合も削除だけを再試行します。
+/// それでも削除できない場合は、`rollback`が有効ならコピーを取り消します
+async fn retry_source_removal(
+    src: &Path,
+    dst: &Path,
+    mut err: std::io::Error,
+    mut attempt: u8,
+    rollback: bool,
+) -> std::result::Result<(), MoveError> {
+    while retry::classify(&err) == ErrorClass::Transient && attempt < MAX_MOVE_ATTEMPTS {
+        tokio::time::sleep(retry::backoff(attempt)).await;
+        attempt += 1;
+        match tokio_fs::remove_file(src).await {
+            Ok(()) => return Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => err = e,
         }
+    }
 
-        // 方法3: 同期的なコピー＆削除を試行
-        if let Some(err) = try_sync_copy_remove(src, dst) {
-            last_error = Some(err);
-        } else {
-            return Ok(());
+    // 他のプロセスなどが既に元ファイルを削除していれば、移動先のコピーが唯一のファイル
+    if matches!(tokio_fs::try_exists(src).await, Ok(false)) {
+        return Ok(());
+    }
+    // 元ファイルがコピーと同じ状態で残っている場合のみコピーを取り消す
+    if rollback && source_matches_copy(src, dst).await && tokio_fs::remove_file(dst).await.is_ok() {
+        return Err(MoveError::RolledBack(err));
+    }
+    Err(MoveError::SourceRetained(err))
+}
+
+/// 元ファイルが残っていて、移動先のコピーと同じ大きさか
+async fn source_matches_copy(src: &Path, dst: &Path) -> bool {
+    match (tokio_fs::metadata(src).await, tokio_fs::metadata(dst).await) {
+        (Ok(src_meta), Ok(dst_meta)) => src_meta.is_file() && src_meta.len() == dst_meta.len(),
+        _ => false,
+    }
+}
+
+/// ファイルの移動を1回試行
+async fn try_move(
+    src: &Path,
+    dst: &Path,
+    on_progress: &dyn Fn(u64, u64),
+) -> std::result::Result<(), MoveError> {
+    // 方法1: 同じデバイス上なら名前の変更で移動
+    if space::same_device(src, dst) {
+        match tokio_fs::rename(src, dst).await {
+            Ok(()) => return Ok(()),
+            Err(e) if e.kind() != std::io::ErrorKind::CrossesDevices => return Err(e.into()),
+            // マウントポイントをまたぐ場合などはコピーで移動
+            Err(_) => {}
         }
     }
 
-    // すべての方法が失敗した場合は最後のエラーを返す
-    Err(last_error.unwrap_or_else(|| {
-        std::io::Error::new(std::io::ErrorKind::Other, "ファイル移動に失敗しました")
-    }))
+    // 方法2: 非同期コピー＆削除を試行（コピー済みの場合は再びコピーしない）
+    let err = match try_async_copy_remove(src, dst, on_progress).await {
+        Ok(()) => return Ok(()),
+        Err(MoveError::Failed(e)) => e,
+        Err(e) => return Err(e),
+    };
+    // ファイルシステムが返したエラーは同期的に試しても同じ結果になる
+    if !is_async_unavailable(&err) {
+        return Err(err.into());
+    }
+
+    // 方法3: 非同期のファイル操作を使えない場合のみ、同期的なコピー＆削除を試行
+    try_sync_copy_remove(src, dst)
+}
+
+/// 非同期のファイル操作そのものを使えなかったことによるエラーか
+///
+/// tokioのバックグラウンドタスクを起動できなかった場合などは、OSのエラーコードを
+/// 持たないエラーになります
+fn is_async_unavailable(err: &std::io::Error) -> bool {
+    err.raw_os_error().is_none()
+        && matches!(
+            err.kind(),
+            std::io::ErrorKind::Other | std::io::ErrorKind::Unsupported
+        )
 }
 
 /// 非同期コピー＆削除を試行
 ///
 /// tokioのファイルシステム機能を使用して非同期にファイルをコピーし、削除します
-async fn try_async_copy_remove(src: &Path, dst: &Path) -> std::io::Result<()> {
+async fn try_async_copy_remove(
+    src: &Path,
+    dst: &Path,
+    on_progress: &dyn Fn(u64, u64),
+) -> std::result::Result<(), MoveError> {
     // まず、tokioの非同期FSを使用した方法を試みる
-    tokio_fs::copy(src, dst).await?;
-
-    // ファイル削除を試行
-    if tokio_fs::remove_file(src).await.is_ok() {
-        return Ok(());
+    let size = tokio_fs::metadata(src).await?.len();
+    if size >= LARGE_FILE_THRESHOLD {
+        copy_with_progress(src, dst, size, on_progress).await?;
+    } else {
+        tokio_fs::copy(src, dst).await?;
     }
 
+    // ファイル削除を試行（既に元ファイルが無い場合は移動できたものとする）
+    let err = match tokio_fs::remove_file(src).await {
+        Ok(()) => return Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => e,
+    };
+
     // パーミッション問題を解決してから削除を再試行
-    if try_fix_permissions(src).is_ok() && tokio_fs::remove_file(src).await.is_ok() {
+    if retry::classify(&err) == ErrorClass::Permanent
+        && try_fix_permissions(src).is_ok()
+        && tokio_fs::remove_file(src).await.is_ok()
+    {
         return Ok(());
     }
 
-    // エラーを返す
-    Err(std::io::Error::new(
-        std::io::ErrorKind::PermissionDenied,
-        "ファイルのコピー後に元ファイルを削除できませんでした",
-    ))
+    // コピーは済んでいるため、削除できなかったことを区別して返す
+    Err(MoveError::SourceRetained(err))
+}
+
+/// 進捗を通知しながらファイルをコピー
+///
+/// 大きなファイルを異なるデバイスへコピーする際に、チャンク単位で読み書きして
+/// コピー済みのバイト数を通知します
+async fn copy_with_progress(
+    src: &Path,
+    dst: &Path,
+    size: u64,
+    on_progress: &dyn Fn(u64, u64),
+) -> std::io::Result<u64> {
+    let mut reader = tokio_fs::File::open(src).await?;
+    let mut writer = tokio_fs::File::create(dst).await?;
+    let mut buffer = vec![0u8; COPY_CHUNK_SIZE];
+    let mut copied = 0u64;
+
+    on_progress(0, size);
+    loop {
+        let read = reader.read(&mut buffer).await?;
+        if read == 0 {
+            break;
+        }
+        writer.write_all(&buffer[..read]).await?;
+        copied += read as u64;
+        on_progress(copied, size);
+    }
+    writer.flush().await?;
+
+    // tokio_fs::copy と同様にパーミッションも引き継ぐ
+    let permissions = tokio_fs::metadata(src).await?.permissions();
+    tokio_fs::set_permissions(dst, permissions).await?;
+
+    Ok(copied)
 }
 
 /// 同期的なコピー＆削除を試行
 ///
 /// 標準ライブラリのファイル操作機能を使用して同期的にファイルをコピーし、削除します
-fn try_sync_copy_remove(src: &Path, dst: &Path) -> Option<std::io::Error> {
-    match fs::copy(src, dst) {
-        Ok(_) => {
-            // 削除を試行
-            if fs::remove_file(src).is_ok() {
-                return None;
-            }
-
-            // macOSでの権限エラーの場合、コマンドラインツールを試す
-            #[cfg(target_os = "macos")]
-            if let (Some(src_str), Some(dst_str)) = (src.to_str(), dst.to_str()) {
-                if std::process::Command::new("mv")
-                    .arg("-f")
-                    .arg(src_str)
-                    .arg(dst_str)
-                    .output()
-                    .ok()
-                    .filter(|output| output.status.success())
-                    .is_some()
-                {
-                    return None;
-                }
-            }
+fn try_sync_copy_remove(src: &Path, dst: &Path) -> std::result::Result<(), MoveError> {
+    fs::copy(src, dst)?;
+
+    // 削除を試行（既に元ファイルが無い場合は移動できたものとする）
+    let err = match fs::remove_file(src) {
+        Ok(()) => return Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => e,
+    };
 
-            Some(std::io::Error::new(
-                std::io::ErrorKind::PermissionDenied,
-                "ファイルのコピー後に元ファイルを削除できませんでした",
-            ))
-        }
-        Err(e) => Some(e),
+    // macOSでの権限エラーの場合、コマンドラインツールを試す
+    #[cfg(target_os = "macos")]
+    if retry::classify(&err) == ErrorClass::Permanent
+        && std::process::Command::new("mv")
+            .arg("-f")
+            .arg(src)
+            .arg(dst)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .is_some()
+    {
+        return Ok(());
     }
+
+    Err(MoveError::SourceRetained(err))
 }
 
 /// ファイルのパーミッション問題を解決しようとする
@@ -409,9 +1147,14 @@ fn try_fix_permissions(path: &Path) -> std::io::Result<()> {
 /// 互換性のために同期バージョンも維持
 ///
 /// 非同期関数をブロッキング方式で呼び出すためのラッパー
-fn move_file(src: &Path, dst: &Path) -> std::io::Result<()> {
+fn move_file(
+    src: &Path,
+    dst: &Path,
+    rollback: bool,
+    on_progress: &dyn Fn(u64, u64),
+) -> std::result::Result<(), MoveError> {
     let runtime = tokio::runtime::Runtime::new().unwrap();
-    runtime.block_on(move_file_async(src, dst))
+    runtime.block_on(move_file_async(src, dst, rollback, on_progress))
 }
 
 /// パスがカテゴリフォルダ内かどうかチェック
@@ -421,9 +1164,9 @@ fn is_in_category_folder(path: &Path, output_folder: &Path, category_names: &[St
     // 出力フォルダからの相対パスを取得
     if let Some(rel_path) = path.strip_prefix(output_folder).ok() {
         // 最初のコンポーネント（フォルダ名）を取得
-        if let Some(component_str) = rel_path.components().next().and_then(|comp| {
+        if let Some(component) = rel_path.components().next().and_then(|comp| {
             if let std::path::Component::Normal(component) = comp {
-                component.to_str()
+                Some(component)
             } else {
                 None
             }
@@ -431,7 +1174,7 @@ fn is_in_category_folder(path: &Path, output_folder: &Path, category_names: &[St
             // そのフォルダ名がカテゴリ名と一致するか確認
             return category_names
                 .iter()
-                .any(|category| category == component_str);
+                .any(|category| OsStr::new(category) == component);
         }
     }
     false
@@ -439,131 +1182,200 @@ fn is_in_category_folder(path: &Path, output_folder: &Path, category_names: &[St
 
 /// 出力フォルダにあるカテゴリフォルダ内のファイルをフィルタリング
 ///
-/// 入力ファイルリストから、既に出力フォルダのカテゴリ内にあるファイルを除外します
+/// 入力ファイルリストを、処理対象のファイルと既にいずれかの出力フォルダの
+/// カテゴリ内にあるファイル（スキップ対象）に分けます
 fn filter_output_category_files(
-    files: Vec<String>,
-    output_folder: &str,
+    files: Vec<IpcPath>,
+    output_roots: &[PathBuf],
     category_names: &[String],
-) -> Vec<String> {
-    let output_path = Path::new(output_folder);
+) -> (Vec<IpcPath>, Vec<IpcPath>) {
+    files.into_iter().partition(|file_path| {
+        let path = file_path.as_path();
+        // パスが出力フォルダから始まる場合、カテゴリフォルダ内かチェック
+        !output_roots.iter().any(|output_root| {
+            path.starts_with(output_root)
+                && is_in_category_folder(path, output_root, category_names)
+        })
+    })
+}
 
+/// スキップしたファイルの処理結果を作成
+fn skipped_results(files: Vec<IpcPath>) -> Vec<FileResult> {
     files
         .into_iter()
-        .filter(|file_path| {
-            let path = Path::new(file_path);
-            if path.starts_with(output_folder) {
-                // パスが出力フォルダから始まる場合、カテゴリフォルダ内かチェック
-                !is_in_category_folder(path, output_path, category_names)
-            } else {
-                true
-            }
+        .map(|file_path| {
+            FileResult::skipped(file_path, "既にカテゴリフォルダ内にあります".to_string())
         })
         .collect()
 }
 
+/// 連番付きのファイル名を作成（`name.ext` → `name_1.ext`）
+fn numbered_file_name(path: &Path, counter: u32, compounds: &[String]) -> OsString {
+    // 既知の複合拡張子（`archive.tar.gz`）は分けずに、連番を拡張子の前に付ける
+    if let Some(name) = path.file_name().and_then(OsStr::to_str) {
+        let (stem, ext) = extensions::split(name, compounds);
+        return OsString::from(format!("{}_{}{}", stem, counter, ext));
+    }
+
+    let mut new_name = path.file_stem().unwrap_or_default().to_os_string();
+    new_name.push(format!("_{}", counter));
+    if let Some(ext) = path.extension() {
+        new_name.push(".");
+        new_name.push(ext);
+    }
+    new_name
+}
+
+/// 整理ジョブの中で使い回す情報
+///
+/// ジョブごとに作り、そのジョブのファイルの処理でのみ共有します
+#[derive(Default)]
+struct OrganizeContext {
+    /// 移動先フォルダごとの既存のファイル名
+    names: NameIndex,
+    /// 移動先フォルダごとのファイルシステム
+    file_systems: HashMap<PathBuf, FileSystem>,
+    /// 移動先の容量不足を検出したか（検出後は残りのファイルを処理しない）
+    disk_full: bool,
+}
+
 /// 単一ファイルを処理してカテゴリフォルダに移動
 ///
-/// ファイルの拡張子に基づいて適切なカテゴリを判断し、そのカテゴリフォルダに移動します
+/// ファイルの拡張子に基づいて適切なカテゴリを判断し、そのカテゴリフォルダに移動します。
+/// ファイルが設定済みの入力元に属する場合は、その入力元の出力先とルールを使用します。
+/// カテゴリに出力先が設定されている場合は、そちらを優先します。
+/// UTF-8として解釈できないファイル名もそのまま扱い、結果のメッセージで報告します
 fn process_single_file(
-    file_path: &str,
-    output_path: &Path,
-    categories: &HashMap<String, Vec<String>>,
+    file_path: &IpcPath,
+    default_output: &Path,
+    config: &Config,
+    context: &mut OrganizeContext,
+    on_progress: &dyn Fn(u64, u64),
 ) -> FileResult {
-    let path = Path::new(file_path);
+    let path = file_path.as_path();
 
     // ファイルが存在しない場合
     if !path.exists() {
-        return FileResult::error(file_path.to_string(), "ファイルが存在しません".to_string());
+        return FileResult::error(file_path.clone(), "ファイルが存在しません".to_string());
     }
 
-    // カテゴリを取得
-    let ext = path
-        .extension()
-        .and_then(|e| e.to_str())
-        .map(|e| format!(".{}", e))
-        .unwrap_or_else(String::new);
-
-    let category = get_category(&ext, categories);
+    // カテゴリと出力先を取得
+    let Some(category) = config.category_for(path) else {
+        return FileResult::skipped(
+            file_path.clone(),
+            "該当するカテゴリがないため移動しませんでした".to_string(),
+        );
+    };
+    let output_path = config.output_root_for(path, &category, default_output);
 
     // カテゴリフォルダを作成
-    let category_dir = output_path.join(&category);
+    let category_dir = classify::category_dir(&output_path, &category);
     if !category_dir.exists() {
         if let Err(e) = fs::create_dir_all(&category_dir) {
-            return FileResult::error(file_path.to_string(), format!("フォルダ作成エラー: {}", e));
+            return FileResult::error(file_path.clone(), format!("フォルダ作成エラー: {}", e));
         }
     }
 
-    // 移動先のパスを作成
-    let file_name = match path.file_name().and_then(|n| n.to_str()) {
-        Some(name) => name,
-        None => return FileResult::error(file_path.to_string(), "無効なファイル名".to_string()),
+    // 移動先のパスを作成（カテゴリのルールに従って名前を変更）
+    if path.file_name().is_none() {
+        return FileResult::error(file_path.clone(), "無効なファイル名".to_string());
+    }
+    let compounds = config.compound_extensions();
+    let file_name = config.collisions.file_name(rename::apply(
+        config.rename_rules_for(&category),
+        path,
+        &compounds,
+    ));
+
+    // 移動先のファイルシステムで使えない名前は置き換えるか、移動しない
+    let file_system = context
+        .file_systems
+        .entry(category_dir.clone())
+        .or_insert_with(|| FileSystem::detect(&category_dir));
+    let (file_name, sanitize_reasons) = match config.sanitize.sanitize(&file_name, file_system) {
+        Ok(result) => result,
+        Err(reason) => return FileResult::error(file_path.clone(), reason),
     };
-
-    let mut dest_path = category_dir.join(file_name);
-
-    // 既に同名ファイルがある場合は連番を付与
-    let mut counter = 1;
-    while dest_path.exists() {
-        let stem = match path.file_stem().and_then(|s| s.to_str()) {
-            Some(s) => s,
-            None => {
-                return FileResult::error(file_path.to_string(), "無効なファイル名".to_string())
-            }
-        };
-
-        let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
-        let new_name = format!("{}_{}.{}", stem, counter, ext);
-        dest_path = category_dir.join(new_name);
-        counter += 1;
+    let renamed = path.file_name() != Some(file_name.as_os_str());
+
+    // 既に同名（正規化して同じ名前を含む）のファイルがある場合は連番を付与し、
+    // 連番を付けた後の名前で長さを確認
+    let dest_path =
+        config
+            .collisions
+            .destination(&mut context.names, &category_dir, &file_name, &compounds);
+    if let Err(reason) = sanitize::check_path_length(&dest_path) {
+        return FileResult::error(file_path.clone(), reason);
     }
 
-    // ファイルを移動
-    match move_file(path, &dest_path) {
-        Ok(_) => FileResult::success(file_path.to_string(), format!("{}に移動", category)),
-        Err(e) => FileResult::error(file_path.to_string(), format!("移動エラー: {}", e)),
-    }
-}
+    // UTF-8として解釈できない名前は移動できても報告する
+    let name_note = if file_path.is_undecodable() {
+        "（ファイル名をUTF-8として解釈できません）"
+    } else {
+        ""
+    };
 
-/// 処理の進捗状況
-#[derive(Serialize, Clone, Debug)]
-struct OrganizeProgress {
-    /// 処理対象の総ファイル数
-    total_files: usize,
-    /// 処理済みファイル数
-    processed_files: usize,
-    /// 現在の処理結果
-    current_result: Option<FileResult>,
-    /// 処理が完了したかどうか
-    finished: bool,
-    /// バッチ処理モードか
-    batch_progress: bool,
-}
-
-/// 進捗通知を送信
-fn send_progress_notification(
-    window: &tauri::Window,
-    total_files: usize,
-    processed_files: usize,
-    current_result: Option<FileResult>,
-    finished: bool,
-) {
-    let _ = window.emit(
-        "organize-progress",
-        OrganizeProgress {
-            total_files,
-            processed_files,
-            current_result,
-            finished,
-            batch_progress: true,
-        },
+    // ファイルを移動
+    let moved = move_file(
+        path,
+        &dest_path,
+        config.rollback_retained_copies,
+        on_progress,
     );
+    if matches!(moved, Ok(()) | Err(MoveError::SourceRetained(_))) {
+        config.collisions.record(&mut context.names, &dest_path);
+    }
+    match moved {
+        Ok(_) => {
+            let rename_note = if renamed {
+                let reasons = if sanitize_reasons.is_empty() {
+                    String::new()
+                } else {
+                    format!(": {}", sanitize_reasons.join("、"))
+                };
+                format!(
+                    "（{}に名前を変更{}）",
+                    dest_path.file_name().unwrap_or_default().to_string_lossy(),
+                    reasons
+                )
+            } else {
+                String::new()
+            };
+            let message = format!("{}に移動{}{}", category, rename_note, name_note);
+            FileResult::success(file_path.clone(), category, message)
+        }
+        Err(MoveError::SourceRetained(e)) => {
+            let message = format!(
+                "{}にコピーしましたが元ファイルを削除できませんでした: {}{}",
+                category, e, name_note
+            );
+            FileResult::source_retained(file_path.clone(), category, message)
+        }
+        Err(MoveError::RolledBack(e)) => FileResult::error(
+            file_path.clone(),
+            format!(
+                "元ファイルを削除できないためコピーを取り消しました: {}{}",
+                e, name_note
+            ),
+        ),
+        Err(MoveError::Failed(e)) if space::is_disk_full_error(&e) => {
+            context.disk_full = true;
+            FileResult::error(
+                file_path.clone(),
+                format!("移動先の空き容量が不足しています{}", name_note),
+            )
+        }
+        Err(MoveError::Failed(e)) => {
+            FileResult::error(file_path.clone(), format!("移動エラー: {}{}", e, name_note))
+        }
+    }
 }
 
 /// キャンセル時の処理
-fn handle_cancel(window: &tauri::Window, total_files: usize, processed_files: usize) -> bool {
+fn handle_cancel(reporter: &ProgressReporter) -> bool {
     if CANCEL_FLAG.load(Ordering::SeqCst) {
         // 処理中断を通知
-        send_progress_notification(window, total_files, processed_files, None, true);
+        reporter.finish();
         return true;
     }
     false
@@ -580,28 +1392,39 @@ fn cancel_processing() -> std::result::Result<(), String> {
 /// ファイルを整理
 #[tauri::command]
 fn organize_files(
-    files: Vec<String>,
+    files: Vec<IpcPath>,
     output_folder: String,
     config: Config,
+    force: Option<bool>,
 ) -> std::result::Result<Vec<FileResult>, String> {
     let result: Result<Vec<FileResult>> = (|| {
         let output_path = Path::new(&output_folder);
-        if !output_path.exists() {
-            fs::create_dir_all(output_path)?;
-        }
 
         // カテゴリ名のリストを取得
         let category_names = get_category_names(&config);
 
         // 出力先フォルダのカテゴリ内ファイルをフィルタリング
-        let filtered_files = filter_output_category_files(files, &output_folder, &category_names);
-
-        let mut results = Vec::new();
+        let output_roots = get_output_roots(&config, output_path);
+        let (filtered_files, skipped_files) =
+            filter_output_category_files(files, &output_roots, &category_names);
+
+        // 使用する出力先と空き容量を事前に確認
+        routing::validate_output_roots(&config, &filtered_files, output_path)?;
+        let space_check = check_space(&config, &filtered_files, output_path);
+        if !space_check.sufficient && !force.unwrap_or(false) {
+            return Err(AppError::Custom(space_check.message()));
+        }
+        let mut results = skipped_results(skipped_files);
 
-        // 各ファイルを処理
+        // 各ファイルを処理（容量不足になった場合は残りを処理しない）
+        let mut context = OrganizeContext::default();
         for file_path in filtered_files {
-            let result = process_single_file(&file_path, output_path, &config.categories);
+            let result =
+                process_single_file(&file_path, output_path, &config, &mut context, &|_, _| {});
             results.push(result);
+            if context.disk_full {
+                break;
+            }
         }
 
         Ok(results)
@@ -610,182 +1433,305 @@ fn organize_files(
     to_string_error(result)
 }
 
-/// 入力フォルダからファイルを読み込む
-#[tauri::command]
-fn load_files_from_input_folder(
-    config: Config,
+/// 入力元フォルダをスキャン
+///
+/// `selected`が指定された場合はそのパスの入力元のみ、指定がない場合は有効な入力元を
+/// 全てスキャンします。存在しない入力元は警告として報告し、他の入力元のスキャンを続けます。
+/// 全ての入力元をスキャンできなかった場合はエラーを返します。
+/// 見つかったファイルや警告は`sink`に渡されます
+fn scan_input_folder(
+    config: &Config,
     recursive: bool,
-) -> std::result::Result<Vec<String>, String> {
-    let result: Result<Vec<String>> = (|| {
-        // 入力フォルダが設定されているか確認
-        let input_folder = config
-            .input_folder
-            .as_ref()
-            .ok_or_else(|| AppError::Custom("入力フォルダが設定されていません".to_string()))?;
+    selected: Option<&[String]>,
+    sink: &mut dyn ScanSink,
+) -> Result<()> {
+    let sources: Vec<InputSource> = config
+        .input_sources(recursive)
+        .into_iter()
+        .filter(|source| match selected {
+            Some(selected) => selected.contains(&source.path),
+            None => source.enabled,
+        })
+        .collect();
 
-        let path = Path::new(input_folder);
+    // 入力フォルダが設定されているか確認
+    if sources.is_empty() {
+        return Err(AppError::Custom(
+            "入力フォルダが設定されていません".to_string(),
+        ));
+    }
 
-        // 入力フォルダの存在確認
-        if !path.exists() {
-            return Err(AppError::Custom(format!(
-                "入力フォルダが存在しません: {}",
-                input_folder
-            )));
-        }
+    // 出力先フォルダのパスを収集
+    let mut output_folders: Vec<PathBuf> =
+        config.output_folders.iter().map(PathBuf::from).collect();
+    output_folders.extend(
+        sources
+            .iter()
+            .filter_map(|source| source.output_folder.as_deref())
+            .chain(config.category_outputs.values().map(String::as_str))
+            .map(PathBuf::from),
+    );
 
-        // ディレクトリかどうか確認
-        if !path.is_dir() {
-            return Err(AppError::Custom(format!(
-                "指定されたパスはディレクトリではありません: {}",
-                input_folder
-            )));
-        }
+    // カテゴリ名のリスト
+    let category_names = get_category_names(config);
 
-        let mut files = Vec::new();
+    // スキャンできなかった入力元の理由
+    let mut failures = Vec::new();
+    for source in &sources {
+        if sink.is_cancelled() {
+            return Ok(());
+        }
 
-        // 出力先フォルダのパスを収集
-        let output_folders: Vec<PathBuf> = config
-            .output_folders
-            .iter()
-            .map(|f| Path::new(f).to_path_buf())
-            .collect();
+        let path = Path::new(&source.path);
 
-        // カテゴリ名のリスト
-        let category_names = get_category_names(&config);
+        // 入力フォルダの存在確認
+        let problem = if !path.exists() {
+            Some("入力フォルダが存在しません")
+        } else if !path.is_dir() {
+            // ディレクトリかどうか確認
+            Some("指定されたパスはディレクトリではありません")
+        } else {
+            None
+        };
+        if let Some(message) = problem {
+            failures.push(format!("{}: {}", message, source.path));
+            sink.warning(ScanWarning {
+                path: IpcPath::from(path),
+                message: message.to_string(),
+            });
+            continue;
+        }
 
         // ファイルを収集
-        collect_files(
+        let scanned = Scanner::new(
             path,
-            &mut files,
-            recursive,
+            source.recursive,
+            &config.scan,
             &output_folders,
             &category_names,
-        )?;
+        )
+        .and_then(|scanner| scanner.run(sink));
+        if let Err(e) = scanned {
+            failures.push(format!("{}: {}", e, source.path));
+            sink.warning(ScanWarning {
+                path: IpcPath::from(path),
+                message: e.to_string(),
+            });
+        }
+    }
 
-        Ok(files)
+    if failures.len() == sources.len() {
+        return Err(AppError::Custom(failures.join("\n")));
+    }
+    Ok(())
+}
+
+/// 入力フォルダの読み込み結果
+#[derive(Serialize, Clone, Debug)]
+struct LoadedFiles {
+    /// 見つかったファイル
+    files: Vec<IpcPath>,
+    /// 読み取れなかった入力元やフォルダ
+    warnings: Vec<ScanWarning>,
+}
+
+/// 入力フォルダからファイルを読み込む
+///
+/// `sources`を指定した場合はそのパスの入力元のみをスキャンします。
+/// 一部の入力元を読み取れなかった場合は、読み取れた入力元のファイルと
+/// 読み取れなかった理由を返します。
+/// 大きなフォルダでは`scan_input_folder_async`を使用してください
+#[tauri::command]
+fn load_files_from_input_folder(
+    config: Config,
+    recursive: bool,
+    sources: Option<Vec<String>>,
+) -> std::result::Result<LoadedFiles, String> {
+    let result: Result<LoadedFiles> = (|| {
+        let mut sink = CollectSink::default();
+        scan_input_folder(&config, recursive, sources.as_deref(), &mut sink)?;
+        Ok(LoadedFiles {
+            files: sink.files.into_iter().map(IpcPath::from).collect(),
+            warnings: sink.warnings,
+        })
     })();
 
     to_string_error(result)
 }
 
-/// ディレクトリからファイルを収集する
+/// 入力フォルダを非同期でスキャン
 ///
-/// 指定されたディレクトリ内のファイルを再帰的または非再帰的に収集します
-fn collect_files(
-    dir: &Path,
-    files: &mut Vec<String>,
+/// スキャンは別スレッドで行い、見つかったファイルを`scan-progress`イベントで
+/// 少しずつ通知します。戻り値のジョブIDは`cancel_scan`で中断する際に使用します
+#[tauri::command]
+fn scan_input_folder_async(
+    config: Config,
     recursive: bool,
-    output_folders: &[PathBuf],
-    category_names: &[String],
-) -> Result<()> {
-    for entry in fs::read_dir(dir)? {
-        let entry = entry?;
-        let path = entry.path();
-
-        // 出力先フォルダの下位階層にあるカテゴリフォルダ内のファイルを除外
-        let should_exclude = output_folders.iter().any(|output_folder| {
-            path.starts_with(output_folder)
-                && is_in_category_folder(&path, output_folder, category_names)
-        });
-
-        if should_exclude {
-            continue;
-        }
+    sources: Option<Vec<String>>,
+    window: tauri::Window,
+) -> std::result::Result<String, String> {
+    let job_id = jobs::new_job_id();
+    scan_job::start(window, &job_id, move |sink| {
+        scan_input_folder(&config, recursive, sources.as_deref(), sink)
+    });
+    Ok(job_id)
+}
 
-        if path.is_file() {
-            // ファイルの場合はリストに追加
-            if let Some(path_str) = path.to_str() {
-                files.push(path_str.to_string());
-            }
-        } else if recursive && path.is_dir() {
-            // ディレクトリで再帰処理が有効な場合は再帰的に収集
-            collect_files(&path, files, recursive, output_folders, category_names)?;
-        }
+/// スキャンを中断
+#[tauri::command]
+fn cancel_scan(job_id: String) -> std::result::Result<(), String> {
+    if scan_job::cancel(&job_id) {
+        Ok(())
+    } else {
+        Err(format!("実行中のスキャンが見つかりません: {}", job_id))
     }
-
-    Ok(())
 }
 
 /// 非同期でファイルを整理
+///
+/// 処理は別スレッドで行い、進捗を`organize-progress`イベントで通知します。
+/// 完了時には集計結果を`organize-summary`イベントで通知し、全ての処理結果を返します
 #[tauri::command]
-fn organize_files_async(
-    files: Vec<String>,
+async fn organize_files_async(
+    files: Vec<IpcPath>,
     output_folder: String,
     config: Config,
+    force: Option<bool>,
     window: tauri::Window,
 ) -> std::result::Result<Vec<FileResult>, String> {
     // 開始時に中断フラグをリセット
     CANCEL_FLAG.store(false, Ordering::SeqCst);
 
-    let result: Result<Vec<FileResult>> = (|| {
+    let job_id = jobs::new_job_id();
+
+    let output_path = Path::new(&output_folder);
+
+    // カテゴリ名のリスト
+    let category_names = get_category_names(&config);
+
+    // フィルタリング
+    let output_roots = get_output_roots(&config, output_path);
+    let (filtered_files, skipped_files) =
+        filter_output_category_files(files, &output_roots, &category_names);
+
+    // 使用する出力先を事前に確認
+    routing::validate_output_roots(&config, &filtered_files, output_path)
+        .map_err(|e| e.to_string())?;
+
+    let total_files = filtered_files.len();
+    let skipped = skipped_results(skipped_files);
+    let total_with_skipped = total_files + skipped.len();
+
+    // 進捗計算のために総バイト数を集計
+    let file_sizes: Vec<u64> = filtered_files
+        .iter()
+        .map(|file_path| fs::metadata(file_path).map(|m| m.len()).unwrap_or(0))
+        .collect();
+    let total_bytes = file_sizes.iter().sum();
+
+    // 移動先の空き容量を確認（`force`が指定された場合は不足していても続ける）
+    let space_check = space::check(&config, &filtered_files, &file_sizes, output_path);
+    if !space_check.sufficient && !force.unwrap_or(false) {
+        return Err(space_check.message());
+    }
+
+    let reporter = ProgressReporter::new(window.clone(), &job_id, total_files, total_bytes);
+
+    // 初期化メッセージを送信
+    reporter.start();
+
+    // 別スレッドで処理し、完了を待つ
+    let (sender, receiver) = tokio::sync::oneshot::channel();
+    let thread_job_id = job_id.clone();
+
+    std::thread::spawn(move || {
+        let started_at = Instant::now();
+        let mut results = skipped;
+        let mut cancelled = false;
         let output_path = Path::new(&output_folder);
-        if !output_path.exists() {
-            fs::create_dir_all(output_path)?;
-        }
+        let mut context = OrganizeContext::default();
 
-        // カテゴリ名のリスト
-        let category_names = get_category_names(&config);
+        for (file_path, &size) in filtered_files.iter().zip(&file_sizes) {
+            // 各ファイル処理前に中断フラグをチェック
+            if handle_cancel(&reporter) {
+                cancelled = true;
+                break;
+            }
+
+            reporter.start_file(file_path, size);
+
+            // ファイルを処理
+            let result = process_single_file(
+                file_path,
+                output_path,
+                &config,
+                &mut context,
+                &|copied, total| reporter.file_progress(copied, total),
+            );
+
+            results.push(result.clone());
+
+            // 進捗を記録（通知は一定間隔に間引かれる）
+            reporter.finish_file(result, size);
 
-        // フィルタリング
-        let filtered_files = filter_output_category_files(files, &output_folder, &category_names);
-
-        let total_files = filtered_files.len();
-
-        // 初期化メッセージを送信
-        send_progress_notification(&window, total_files, 0, None, false);
-
-        // 別スレッドで処理
-        let window_clone = window.clone();
-        let config_clone = config.clone();
-        let output_folder_clone = output_folder.clone();
-
-        std::thread::spawn(move || {
-            let mut results = Vec::new();
-            let mut processed = 0;
-            let output_path = Path::new(&output_folder_clone);
-
-            // バッチサイズごとに処理
-            for batch in filtered_files.chunks(BATCH_SIZE) {
-                // 中断フラグをチェック
-                if handle_cancel(&window_clone, total_files, processed) {
-                    return;
-                }
-
-                for file_path in batch {
-                    // 各ファイル処理前にも中断フラグをチェック
-                    if handle_cancel(&window_clone, total_files, processed) {
-                        return;
-                    }
-
-                    // ファイルを処理
-                    let result =
-                        process_single_file(file_path, output_path, &config_clone.categories);
-
-                    results.push(result.clone());
-                    processed += 1;
-
-                    // 進捗を通知
-                    send_progress_notification(
-                        &window_clone,
-                        total_files,
-                        processed,
-                        Some(result),
-                        processed == total_files,
-                    );
-                }
-
-                // バッチ処理の後に少し待機してUIの更新時間を確保
-                thread::sleep(Duration::from_millis(BATCH_DELAY_MS));
+            // 容量不足になった場合は残りのファイルを処理しない
+            if context.disk_full {
+                break;
             }
+        }
 
+        if !cancelled {
             // 全ての処理が完了したことを通知
-            send_progress_notification(&window_clone, total_files, processed, None, true);
-        });
+            reporter.finish();
+        }
 
-        Ok(Vec::new())
-    })();
+        // 集計結果を通知し、結果を保存
+        let summary = OrganizeSummary::from_results(
+            &thread_job_id,
+            total_with_skipped,
+            &results,
+            started_at.elapsed().as_millis() as u64,
+            cancelled,
+            context.disk_full,
+        );
+        let _ = window.emit("organize-summary", summary);
+        jobs::store_results(&thread_job_id, results.clone());
 
-    to_string_error(result)
+        let _ = sender.send(results);
+    });
+
+    receiver
+        .await
+        .map_err(|_| format!("整理処理が異常終了しました（ジョブID: {}）", job_id))
+}
+
+/// 整理する前に移動先の空き容量を確認
+#[tauri::command]
+fn check_free_space(
+    files: Vec<IpcPath>,
+    output_folder: String,
+    config: Config,
+) -> std::result::Result<SpaceCheck, String> {
+    let output_path = Path::new(&output_folder);
+    let output_roots = get_output_roots(&config, output_path);
+    let (filtered_files, _) =
+        filter_output_category_files(files, &output_roots, &get_category_names(&config));
+    Ok(check_space(&config, &filtered_files, output_path))
+}
+
+/// ファイルの大きさを調べて移動先の空き容量を確認
+fn check_space(config: &Config, files: &[IpcPath], output_path: &Path) -> SpaceCheck {
+    let sizes: Vec<u64> = files
+        .iter()
+        .map(|file_path| fs::metadata(file_path).map(|m| m.len()).unwrap_or(0))
+        .collect();
+    space::check(config, files, &sizes, output_path)
+}
+
+/// 完了したジョブの処理結果を取得
+#[tauri::command]
+fn get_job_results(job_id: String) -> std::result::Result<Vec<FileResult>, String> {
+    jobs::get_results(&job_id).ok_or_else(|| format!("ジョブの結果が見つかりません: {}", job_id))
 }
 
 /// テスト用の挨拶関数
@@ -796,9 +1742,9 @@ fn greet(name: &str) -> String {
 
 /// ファイルのパーミッションを変更する関数
 #[tauri::command]
-fn change_file_permissions(file_path: String, mode: u32) -> std::result::Result<(), String> {
+fn change_file_permissions(file_path: IpcPath, mode: u32) -> std::result::Result<(), String> {
     let result: Result<()> = (|| {
-        let path = Path::new(&file_path);
+        let path = file_path.as_path();
         if !path.exists() {
             return Err(AppError::Custom("ファイルが存在しません".to_string()));
         }
@@ -843,6 +1789,11 @@ pub fn run() {
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_macos_permissions::init())
+        .setup(|app| {
+            // 設定ファイルの変更を監視
+            config_watch::start(app.handle().clone());
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             greet,
             organize_files,
@@ -850,9 +1801,35 @@ pub fn run() {
             save_config,
             organize_files_async,
             cancel_processing,
+            check_free_space,
+            get_job_results,
+            check_config_folders,
+            list_profiles,
+            switch_profile,
+            delete_profile,
             add_output_folder,
+            remove_output_folder,
+            move_output_folder,
             set_input_folder,
+            clear_input_folder,
+            add_input_source,
+            remove_input_source,
+            set_category_output,
+            set_fallback,
+            set_collision_options,
+            set_sanitize_options,
+            set_rollback_retained_copies,
+            merge_catalogue,
+            list_review_queue,
+            suggest_categories,
+            apply_suggestions,
+            export_rule_set,
+            import_rule_set,
+            set_shared_rules_dir,
+            list_shared_rule_sets,
             load_files_from_input_folder,
+            scan_input_folder_async,
+            cancel_scan,
             change_file_permissions
         ])
         .run(tauri::generate_context!())
@@ -0,0 +1,150 @@
+// 設定ファイルの移行
+//
+// 設定ファイルには形式のバージョンを記録し、古い形式のファイルを読み込んだ場合は
+// バージョンごとの移行処理を順に適用して現在の形式に変換します。
+// 移行前のファイルは上書きせずにバックアップとして残します。
+
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::{AppError, Result};
+
+/// 現在の設定ファイルの形式のバージョン
+pub const CURRENT_CONFIG_VERSION: u32 = 1;
+
+/// 1つ前のバージョンから移行する処理（添字が移行元のバージョン）
+type Migration = fn(&mut serde_json::Map<String, Value>);
+
+const MIGRATIONS: [Migration; CURRENT_CONFIG_VERSION as usize] = [migrate_v0_to_v1];
+
+/// 設定ファイルに記録されたバージョンを取得（記録がない場合は0）
+pub fn config_version(value: &Value) -> u32 {
+    value
+        .get("version")
+        .and_then(Value::as_u64)
+        .and_then(|version| u32::try_from(version).ok())
+        .unwrap_or(0)
+}
+
+/// 設定を現在の形式に移行
+///
+/// 移行元のバージョンを返します。現在より新しいバージョンの設定は扱えないためエラーにします
+pub fn migrate(value: &mut Value) -> Result<u32> {
+    let from_version = config_version(value);
+    if from_version > CURRENT_CONFIG_VERSION {
+        return Err(AppError::Custom(format!(
+            "設定ファイルのバージョン({})はこのアプリより新しいため読み込めません",
+            from_version
+        )));
+    }
+
+    // オブジェクトでない設定は壊れたファイルとして扱う（読み込み時にバックアップから復元する）
+    let map = value
+        .as_object_mut()
+        .ok_or_else(|| AppError::Json(serde::de::Error::custom("設定ファイルの形式が不正です")))?;
+    for migration in &MIGRATIONS[from_version as usize..] {
+        migration(map);
+    }
+    map.insert("version".to_string(), Value::from(CURRENT_CONFIG_VERSION));
+
+    Ok(from_version)
+}
+
+/// 移行前の設定ファイルをバックアップ
+///
+/// `config.json.v0.bak`のように移行元のバージョンを付けた名前でコピーします
+pub fn backup_before_migration(config_path: &Path, from_version: u32) -> Result<PathBuf> {
+    let mut backup_name = config_path
+        .file_name()
+        .map(|name| name.to_os_string())
+        .unwrap_or_default();
+    backup_name.push(format!(".v{}.bak", from_version));
+    let backup_path = config_path.with_file_name(backup_name);
+
+    fs::copy(config_path, &backup_path)?;
+    Ok(backup_path)
+}
+
+/// バージョン0（バージョン記録なし）から1への移行
+///
+/// 先頭の`.`がない拡張子を補い、空文字列の入力フォルダを未設定として扱います
+fn migrate_v0_to_v1(map: &mut serde_json::Map<String, Value>) {
+    if let Some(Value::Object(categories)) = map.get_mut("categories") {
+        for extensions in categories.values_mut() {
+            if let Value::Array(extensions) = extensions {
+                for extension in extensions.iter_mut() {
+                    if let Value::String(text) = extension {
+                        let trimmed = text.trim();
+                        *text = if trimmed.starts_with('.') {
+                            trimmed.to_string()
+                        } else {
+                            format!(".{}", trimmed)
+                        };
+                    }
+                }
+            }
+        }
+    }
+
+    if map.get("input_folder").and_then(Value::as_str) == Some("") {
+        map.insert("input_folder".to_string(), Value::Null);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn v0_config_is_migrated_to_the_current_version() {
+        let mut value = json!({
+            "categories": { "Images": ["jpg", " .png "] },
+            "input_folder": "",
+        });
+        assert_eq!(migrate(&mut value).unwrap(), 0);
+        assert_eq!(config_version(&value), CURRENT_CONFIG_VERSION);
+        assert_eq!(value["categories"]["Images"], json!([".jpg", ".png"]));
+        assert!(value["input_folder"].is_null());
+    }
+
+    #[test]
+    fn current_config_is_left_unchanged() {
+        let mut value = json!({
+            "version": CURRENT_CONFIG_VERSION,
+            "categories": { "Images": [".jpg"] },
+            "input_folder": "/in",
+        });
+        let expected = value.clone();
+        assert_eq!(migrate(&mut value).unwrap(), CURRENT_CONFIG_VERSION);
+        assert_eq!(value, expected);
+    }
+
+    #[test]
+    fn newer_version_is_rejected() {
+        let mut value = json!({ "version": CURRENT_CONFIG_VERSION + 1 });
+        assert!(matches!(migrate(&mut value), Err(AppError::Custom(_))));
+    }
+
+    #[test]
+    fn non_object_config_is_treated_as_corrupt() {
+        for mut value in [json!([]), json!("config"), json!(null)] {
+            assert!(matches!(migrate(&mut value), Err(AppError::Json(_))));
+        }
+    }
+
+    #[test]
+    fn backup_keeps_the_original_file() {
+        let dir = std::env::temp_dir().join(format!("migration-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let config_path = dir.join("config.json");
+        fs::write(&config_path, "{}").unwrap();
+
+        let backup_path = backup_before_migration(&config_path, 0).unwrap();
+        assert_eq!(backup_path, dir.join("config.json.v0.bak"));
+        assert_eq!(fs::read_to_string(&backup_path).unwrap(), "{}");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,244 @@
+// 設定ファイルの場所とプロファイル
+//
+// 設定ファイルはOSごとの設定フォルダ（LinuxではXDG_CONFIG_HOME、通常は~/.config）の
+// `shiwakekun`フォルダに保存します。用途ごとに名前付きのプロファイル（「仕事」「写真」など）を
+// 作成して切り替えられます。環境変数やコマンドライン引数で設定ファイルのパスや
+// 使用するプロファイルを指定することもできます。
+
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, Once};
+
+use crate::{AppError, Result};
+
+/// 設定ファイルのパスを指定する環境変数
+const CONFIG_PATH_ENV: &str = "SHIWAKEKUN_CONFIG";
+
+/// 使用するプロファイルを指定する環境変数
+const PROFILE_ENV: &str = "SHIWAKEKUN_PROFILE";
+
+/// 設定ファイルのパスを指定するコマンドライン引数
+const CONFIG_PATH_ARG: &str = "--config";
+
+/// 使用するプロファイルを指定するコマンドライン引数
+const PROFILE_ARG: &str = "--profile";
+
+/// 設定フォルダ内のアプリのフォルダ名
+const APP_DIR_NAME: &str = "shiwakekun";
+
+/// 既定のプロファイルの設定ファイル名
+const CONFIG_FILE_NAME: &str = "config.json";
+
+/// 名前付きプロファイルを保存するフォルダ名
+const PROFILES_DIR_NAME: &str = "profiles";
+
+/// 最後に使用したプロファイル名を記録するファイル名
+const ACTIVE_PROFILE_FILE_NAME: &str = "active-profile";
+
+/// 以前の設定ファイル名（ホームフォルダ直下）
+const LEGACY_CONFIG_FILE_NAME: &str = ".shiwakekunrc.json";
+
+/// 使用中のプロファイル（Noneは既定のプロファイル）
+static ACTIVE_PROFILE: Mutex<Option<String>> = Mutex::new(None);
+
+/// 使用中のプロファイルの初期化
+static ACTIVE_PROFILE_INIT: Once = Once::new();
+
+/// プロファイルの一覧
+#[derive(Serialize, Clone, Debug)]
+pub struct ProfileList {
+    /// 使用中のプロファイル（Noneは既定のプロファイル）
+    pub active: Option<String>,
+    /// 名前付きプロファイル
+    pub profiles: Vec<String>,
+    /// 使用中の設定ファイルのパス
+    pub config_path: String,
+    /// 設定ファイルのパスが環境変数・引数で固定されているか
+    pub path_overridden: bool,
+}
+
+/// 使用中の設定ファイルのパスを取得
+pub fn config_path() -> Result<PathBuf> {
+    if let Some(path) = override_path() {
+        return Ok(path);
+    }
+    profile_path(active_profile().as_deref())
+}
+
+/// 以前の設定ファイル（~/.shiwakekunrc.json）を新しい場所に移動
+///
+/// 既定のプロファイルの設定ファイルがまだない場合のみ移動し、移動先のパスを返します
+pub fn migrate_legacy_config() -> Result<Option<PathBuf>> {
+    if override_path().is_some() {
+        return Ok(None);
+    }
+    let Some(legacy_path) = dirs::home_dir().map(|home| home.join(LEGACY_CONFIG_FILE_NAME)) else {
+        return Ok(None);
+    };
+    let new_path = profile_path(None)?;
+    if !legacy_path.is_file() || new_path.exists() {
+        return Ok(None);
+    }
+
+    if let Some(parent) = new_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::copy(&legacy_path, &new_path)?;
+    fs::remove_file(&legacy_path)?;
+    Ok(Some(new_path))
+}
+
+/// プロファイルの一覧を取得
+pub fn list_profiles() -> Result<ProfileList> {
+    let mut profiles = Vec::new();
+    let profiles_dir = app_config_dir()?.join(PROFILES_DIR_NAME);
+    if profiles_dir.is_dir() {
+        for entry in fs::read_dir(&profiles_dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                if let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) {
+                    profiles.push(name.to_string());
+                }
+            }
+        }
+    }
+    profiles.sort();
+
+    Ok(ProfileList {
+        active: active_profile(),
+        profiles,
+        config_path: config_path()?.display().to_string(),
+        path_overridden: override_path().is_some(),
+    })
+}
+
+/// 使用するプロファイルを切り替え（Noneは既定のプロファイル）
+///
+/// 切り替えたプロファイルは次回起動時にも使用されます
+pub fn switch_profile(name: Option<String>) -> Result<()> {
+    if override_path().is_some() {
+        return Err(AppError::Custom(
+            "設定ファイルのパスが指定されているため、プロファイルを切り替えられません".to_string(),
+        ));
+    }
+    if let Some(name) = &name {
+        validate_profile_name(name)?;
+    }
+
+    let dir = app_config_dir()?;
+    fs::create_dir_all(&dir)?;
+    let marker = dir.join(ACTIVE_PROFILE_FILE_NAME);
+    match &name {
+        Some(name) => fs::write(&marker, name)?,
+        None if marker.exists() => fs::remove_file(&marker)?,
+        None => {}
+    }
+
+    // 起動時の初期化で上書きされないよう、初期化を済ませてから更新する
+    active_profile();
+    *lock_profile() = name;
+    Ok(())
+}
+
+/// プロファイルを削除（使用中のプロファイルは削除できない）
+pub fn delete_profile(name: &str) -> Result<()> {
+    validate_profile_name(name)?;
+    if active_profile().as_deref() == Some(name) {
+        return Err(AppError::Custom(
+            "使用中のプロファイルは削除できません".to_string(),
+        ));
+    }
+
+    let path = profile_path(Some(name))?;
+    if path.exists() {
+        fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+/// 使用中のプロファイルを取得
+fn active_profile() -> Option<String> {
+    ACTIVE_PROFILE_INIT.call_once(|| {
+        *lock_profile() = initial_profile();
+    });
+    lock_profile().clone()
+}
+
+/// 起動時のプロファイル（引数、環境変数、前回使用したプロファイルの順に優先）
+fn initial_profile() -> Option<String> {
+    let requested = arg_value(PROFILE_ARG)
+        .or_else(|| std::env::var(PROFILE_ENV).ok())
+        .or_else(|| {
+            let marker = app_config_dir().ok()?.join(ACTIVE_PROFILE_FILE_NAME);
+            fs::read_to_string(marker).ok()
+        })?;
+    let name = requested.trim().to_string();
+    validate_profile_name(&name).ok().map(|_| name)
+}
+
+/// 環境変数・引数で指定された設定ファイルのパス
+fn override_path() -> Option<PathBuf> {
+    arg_value(CONFIG_PATH_ARG)
+        .or_else(|| std::env::var(CONFIG_PATH_ENV).ok())
+        .filter(|path| !path.is_empty())
+        .map(PathBuf::from)
+}
+
+/// プロファイルの設定ファイルのパス
+fn profile_path(name: Option<&str>) -> Result<PathBuf> {
+    let dir = app_config_dir()?;
+    Ok(match name {
+        Some(name) => dir.join(PROFILES_DIR_NAME).join(format!("{}.json", name)),
+        None => dir.join(CONFIG_FILE_NAME),
+    })
+}
+
+/// アプリの設定フォルダ
+///
+/// OSの設定フォルダが分からない場合はホームフォルダの`.config`を使用します
+fn app_config_dir() -> Result<PathBuf> {
+    dirs::config_dir()
+        .or_else(|| dirs::home_dir().map(|home| home.join(".config")))
+        .map(|dir| dir.join(APP_DIR_NAME))
+        .ok_or_else(|| AppError::Custom("設定フォルダの場所を特定できません".to_string()))
+}
+
+/// コマンドライン引数の値（`--name value`または`--name=value`）
+fn arg_value(name: &str) -> Option<String> {
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == name {
+            return args.next();
+        }
+        if let Some(value) = arg
+            .strip_prefix(name)
+            .and_then(|rest| rest.strip_prefix('='))
+        {
+            return Some(value.to_string());
+        }
+    }
+    None
+}
+
+/// プロファイル名として使えるか確認
+///
+/// ファイル名として使えない文字や、`.`で始まる名前は使用できません
+fn validate_profile_name(name: &str) -> Result<()> {
+    let invalid = name.is_empty()
+        || name.starts_with('.')
+        || name.chars().any(|c| {
+            c.is_control() || matches!(c, '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|')
+        });
+    if invalid || Path::new(name).components().count() != 1 {
+        return Err(AppError::Custom(format!(
+            "プロファイル名に使用できない文字が含まれています: {}",
+            name
+        )));
+    }
+    Ok(())
+}
+
+fn lock_profile() -> std::sync::MutexGuard<'static, Option<String>> {
+    ACTIVE_PROFILE.lock().unwrap_or_else(|e| e.into_inner())
+}
@@ -0,0 +1,198 @@
+// 整理処理の進捗通知
+//
+// 処理済みファイル数だけでなく、バイト数・スループット・残り時間を計算して
+// フロントエンドに通知します。小さなファイルが大量にある場合でもUIが
+// イベントで溢れないよう、通知の頻度を間引きます。
+
+use serde::Serialize;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::Emitter;
+
+use crate::{FileResult, IpcPath};
+
+/// 進捗イベント名
+const PROGRESS_EVENT: &str = "organize-progress";
+
+/// 進捗通知の最小間隔（ミリ秒）
+const EMIT_INTERVAL_MS: u64 = 100;
+
+/// スループット計算を始めるまでの最小経過時間（ミリ秒）
+const MIN_ELAPSED_FOR_RATE_MS: u64 = 500;
+
+/// 処理の進捗状況
+#[derive(Serialize, Clone, Debug)]
+pub struct OrganizeProgress {
+    /// ジョブID
+    pub job_id: String,
+    /// 処理対象の総ファイル数
+    pub total_files: usize,
+    /// 処理済みファイル数
+    pub processed_files: usize,
+    /// 処理対象の総バイト数
+    pub total_bytes: u64,
+    /// 処理済みバイト数（処理中ファイルのコピー済み分を含む）
+    pub processed_bytes: u64,
+    /// 現在処理中のファイル
+    pub current_file: Option<IpcPath>,
+    /// 処理中ファイルのコピー済みバイト数
+    pub current_file_bytes: u64,
+    /// 処理中ファイルの総バイト数
+    pub current_file_total_bytes: u64,
+    /// 1秒あたりの処理バイト数
+    pub bytes_per_second: u64,
+    /// 残り時間の推定値（秒）
+    pub eta_seconds: Option<u64>,
+    /// 現在の処理結果（直近に完了したファイル）
+    pub current_result: Option<FileResult>,
+    /// 前回の通知以降に完了したファイルの処理結果
+    pub new_results: Vec<FileResult>,
+    /// 処理が完了したかどうか
+    pub finished: bool,
+    /// バッチ処理モードか
+    pub batch_progress: bool,
+}
+
+/// 集計中の進捗状態
+struct ProgressState {
+    processed_files: usize,
+    processed_bytes: u64,
+    current_file: Option<IpcPath>,
+    current_file_bytes: u64,
+    current_file_total_bytes: u64,
+    pending_results: Vec<FileResult>,
+    last_emit: Option<Instant>,
+}
+
+/// 進捗を集計し、間引きながら通知する
+pub struct ProgressReporter {
+    window: tauri::Window,
+    job_id: String,
+    total_files: usize,
+    total_bytes: u64,
+    started_at: Instant,
+    state: Mutex<ProgressState>,
+}
+
+impl ProgressReporter {
+    /// 新しい進捗レポーターを作成
+    pub fn new(window: tauri::Window, job_id: &str, total_files: usize, total_bytes: u64) -> Self {
+        Self {
+            window,
+            job_id: job_id.to_string(),
+            total_files,
+            total_bytes,
+            started_at: Instant::now(),
+            state: Mutex::new(ProgressState {
+                processed_files: 0,
+                processed_bytes: 0,
+                current_file: None,
+                current_file_bytes: 0,
+                current_file_total_bytes: 0,
+                pending_results: Vec::new(),
+                last_emit: None,
+            }),
+        }
+    }
+
+    /// 処理開始を通知
+    pub fn start(&self) {
+        let mut state = self.lock();
+        self.emit(&mut state, false);
+    }
+
+    /// ファイルの処理開始を記録
+    pub fn start_file(&self, file_path: &IpcPath, size: u64) {
+        let mut state = self.lock();
+        state.current_file = Some(file_path.clone());
+        state.current_file_bytes = 0;
+        state.current_file_total_bytes = size;
+        self.emit_throttled(&mut state);
+    }
+
+    /// 処理中ファイルのコピー進捗を記録
+    pub fn file_progress(&self, copied: u64, total: u64) {
+        let mut state = self.lock();
+        state.current_file_bytes = copied;
+        state.current_file_total_bytes = total;
+        self.emit_throttled(&mut state);
+    }
+
+    /// ファイルの処理完了を記録
+    pub fn finish_file(&self, result: FileResult, size: u64) {
+        let mut state = self.lock();
+        state.processed_files += 1;
+        state.processed_bytes += size;
+        state.current_file = None;
+        state.current_file_bytes = 0;
+        state.current_file_total_bytes = 0;
+        state.pending_results.push(result);
+        self.emit_throttled(&mut state);
+    }
+
+    /// 処理終了（完了または中断）を通知
+    ///
+    /// 未送信の処理結果はすべてこの通知に含まれます
+    pub fn finish(&self) {
+        let mut state = self.lock();
+        state.current_file = None;
+        self.emit(&mut state, true);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, ProgressState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// 前回の通知から一定時間が経過している場合のみ通知
+    fn emit_throttled(&self, state: &mut ProgressState) {
+        let due = state
+            .last_emit
+            .is_none_or(|last| last.elapsed() >= Duration::from_millis(EMIT_INTERVAL_MS));
+        if due {
+            self.emit(state, false);
+        }
+    }
+
+    fn emit(&self, state: &mut ProgressState, finished: bool) {
+        let processed_bytes = state.processed_bytes + state.current_file_bytes;
+        let elapsed = self.started_at.elapsed();
+
+        let bytes_per_second = if elapsed >= Duration::from_millis(MIN_ELAPSED_FOR_RATE_MS) {
+            (processed_bytes as f64 / elapsed.as_secs_f64()) as u64
+        } else {
+            0
+        };
+
+        let eta_seconds = if finished {
+            Some(0)
+        } else {
+            self.total_bytes
+                .saturating_sub(processed_bytes)
+                .checked_div(bytes_per_second)
+        };
+
+        let new_results = std::mem::take(&mut state.pending_results);
+
+        let _ = self.window.emit(
+            PROGRESS_EVENT,
+            OrganizeProgress {
+                job_id: self.job_id.clone(),
+                total_files: self.total_files,
+                processed_files: state.processed_files,
+                total_bytes: self.total_bytes,
+                processed_bytes,
+                current_file: state.current_file.clone(),
+                current_file_bytes: state.current_file_bytes,
+                current_file_total_bytes: state.current_file_total_bytes,
+                bytes_per_second,
+                eta_seconds,
+                current_result: new_results.last().cloned(),
+                new_results,
+                finished,
+                batch_progress: true,
+            },
+        );
+
+        state.last_emit = Some(Instant::now());
+    }
+}
@@ -0,0 +1,274 @@
+// 整理時のファイル名の変更
+//
+// カテゴリごとに設定した名前の変更ルールを順に適用し、移動先のファイル名を決めます。
+// 拡張子の小文字化、空白の置き換え、ブラウザが付ける`(1)`などの重複の印の削除、
+// Unicodeの正規化（NFC）、日付の接頭辞の付与ができます。
+// サブカテゴリにルールがない場合は親カテゴリのルールを使います。
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::ffi::OsString;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+use unicode_normalization::UnicodeNormalization;
+
+use crate::{classify, extensions, Config};
+
+/// 空白の既定の置き換え先
+const DEFAULT_SPACE_REPLACEMENT: &str = "_";
+
+/// 日付の接頭辞の既定の形式
+const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d_";
+
+/// ファイル名に含めるとフォルダの区切りになる文字
+const PATH_SEPARATORS: [char; 2] = ['/', '\\'];
+
+/// 変更後の名前に含まれる区切り文字の置き換え先
+const SEPARATOR_REPLACEMENT: &str = "_";
+
+/// ファイル名の変更ルール
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RenameRule {
+    /// 拡張子を小文字にする（`IMG.JPG` → `IMG.jpg`）
+    LowercaseExtension,
+    /// 空白（全角を含む）を置き換える
+    ReplaceSpaces {
+        #[serde(default = "default_space_replacement")]
+        with: String,
+    },
+    /// ブラウザが付ける重複の印を取り除く（`report (1).pdf` → `report.pdf`）
+    StripDuplicateSuffix,
+    /// Unicodeの正規化（NFC）を行う
+    NormalizeUnicode,
+    /// 更新日時の接頭辞を付ける（形式はstrftimeの書式）
+    DatePrefix {
+        #[serde(default = "default_date_format")]
+        format: String,
+    },
+}
+
+fn default_space_replacement() -> String {
+    DEFAULT_SPACE_REPLACEMENT.to_string()
+}
+
+fn default_date_format() -> String {
+    DEFAULT_DATE_FORMAT.to_string()
+}
+
+impl RenameRule {
+    /// ルールの設定値を確認（フォルダの区切りになる文字を含む場合はエラー）
+    fn validate(&self) -> std::result::Result<(), String> {
+        let (label, value) = match self {
+            RenameRule::ReplaceSpaces { with } => ("空白の置き換え先", with),
+            RenameRule::DatePrefix { format } => ("日付の形式", format),
+            _ => return Ok(()),
+        };
+        if value.contains(PATH_SEPARATORS) {
+            return Err(format!(
+                "{}に「/」や「\\」は使用できません: {}",
+                label, value
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Config {
+    /// ファイル名の変更ルールを確認
+    pub fn validate_rename_rules(&self) -> std::result::Result<(), String> {
+        for (category, rules) in &self.rename_rules {
+            for rule in rules {
+                rule.validate()
+                    .map_err(|e| format!("{}の名前の変更ルール: {}", category, e))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// カテゴリに適用するファイル名の変更ルール（親カテゴリのルールを引き継ぐ）
+    pub fn rename_rules_for(&self, category: &str) -> &[RenameRule] {
+        classify::ancestors(category)
+            .find_map(|category| self.rename_rules.get(category))
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+}
+
+/// ルールを順に適用した移動先のファイル名
+///
+/// UTF-8として解釈できない名前や、変更後に空・`.`・`..`になる名前は元の名前のままにします。
+/// 変更後の名前に含まれるフォルダの区切り文字は置き換えます。
+/// `compounds`は拡張子としてまとめて扱う複合拡張子です
+pub fn apply(rules: &[RenameRule], path: &Path, compounds: &[String]) -> OsString {
+    let original = path.file_name().unwrap_or_default().to_os_string();
+    if rules.is_empty() {
+        return original;
+    }
+    let Some(name) = original.to_str() else {
+        return original;
+    };
+
+    let renamed = rules
+        .iter()
+        .fold(name.to_string(), |name, rule| {
+            apply_rule(rule, &name, path, compounds)
+        })
+        .replace(PATH_SEPARATORS, SEPARATOR_REPLACEMENT);
+    if renamed.is_empty()
+        || renamed == "."
+        || renamed == ".."
+        || (renamed.starts_with('.') && !name.starts_with('.'))
+    {
+        return original;
+    }
+    OsString::from(renamed)
+}
+
+/// ルールを1つ適用
+fn apply_rule(rule: &RenameRule, name: &str, path: &Path, compounds: &[String]) -> String {
+    let (stem, ext) = extensions::split(name, compounds);
+    match rule {
+        RenameRule::LowercaseExtension => format!("{}{}", stem, ext.to_lowercase()),
+        RenameRule::ReplaceSpaces { with } => {
+            let stem = stem.replace([' ', '\u{3000}'], with);
+            format!("{}{}", stem, ext)
+        }
+        RenameRule::StripDuplicateSuffix => {
+            format!("{}{}", strip_duplicate_suffix(stem), ext)
+        }
+        RenameRule::NormalizeUnicode => name.nfc().collect(),
+        RenameRule::DatePrefix { format } => match date_prefix(format, path) {
+            Some(prefix) if !name.starts_with(&prefix) => format!("{}{}", prefix, name),
+            _ => name.to_string(),
+        },
+    }
+}
+
+/// 末尾の` (1)`、`(2)`、`（3）`などを取り除く
+fn strip_duplicate_suffix(stem: &str) -> &str {
+    for (open, close) in [('(', ')'), ('（', '）')] {
+        let Some(inner) = stem.strip_suffix(close) else {
+            continue;
+        };
+        let Some(index) = inner.rfind(open) else {
+            continue;
+        };
+        let number = &inner[index + open.len_utf8()..];
+        if number.is_empty() || !number.chars().all(|c| c.is_ascii_digit()) {
+            continue;
+        }
+        let stripped = inner[..index].trim_end();
+        if !stripped.is_empty() {
+            return stripped;
+        }
+    }
+    stem
+}
+
+/// ファイルの更新日時から接頭辞を作成（書式が不正な場合はNone）
+fn date_prefix(format: &str, path: &Path) -> Option<String> {
+    let modified = fs::metadata(path).and_then(|meta| meta.modified()).ok()?;
+    let date: DateTime<Local> = modified.into();
+    let mut prefix = String::new();
+    write!(prefix, "{}", date.format(format)).ok()?;
+    Some(prefix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rename(rules: &[RenameRule], name: &str) -> String {
+        let compounds = Config::default().compound_extensions();
+        apply(rules, Path::new(name), &compounds)
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    #[test]
+    fn lowercase_extension_only_changes_the_extension() {
+        let rules = [RenameRule::LowercaseExtension];
+        assert_eq!(rename(&rules, "John.Smith.CV.PDF"), "John.Smith.CV.pdf");
+        assert_eq!(rename(&rules, "IMG_001.JPG"), "IMG_001.jpg");
+        assert_eq!(rename(&rules, "Backup.TAR.GZ"), "Backup.tar.gz");
+    }
+
+    #[test]
+    fn replace_spaces_keeps_the_extension() {
+        let rules = [RenameRule::ReplaceSpaces {
+            with: "_".to_string(),
+        }];
+        assert_eq!(
+            rename(&rules, "my report\u{3000}v2.pdf"),
+            "my_report_v2.pdf"
+        );
+    }
+
+    #[test]
+    fn strip_duplicate_suffix_removes_browser_markers() {
+        let rules = [RenameRule::StripDuplicateSuffix];
+        assert_eq!(rename(&rules, "report (1).pdf"), "report.pdf");
+        assert_eq!(rename(&rules, "report（2）.pdf"), "report.pdf");
+        assert_eq!(rename(&rules, "(1).pdf"), "(1).pdf");
+        assert_eq!(rename(&rules, "report (draft).pdf"), "report (draft).pdf");
+    }
+
+    #[test]
+    fn normalize_unicode_converts_to_nfc() {
+        let rules = [RenameRule::NormalizeUnicode];
+        assert_eq!(rename(&rules, "cafe\u{301}.txt"), "caf\u{e9}.txt");
+    }
+
+    #[test]
+    fn separators_in_the_result_are_replaced() {
+        let rules = [RenameRule::ReplaceSpaces {
+            with: "/".to_string(),
+        }];
+        assert_eq!(rename(&rules, "a b.txt"), "a_b.txt");
+    }
+
+    #[test]
+    fn results_that_are_not_file_names_keep_the_original() {
+        let rules = [RenameRule::ReplaceSpaces {
+            with: String::new(),
+        }];
+        assert_eq!(rename(&rules, " .txt"), " .txt");
+        assert_eq!(rename(&[], "unchanged.txt"), "unchanged.txt");
+    }
+
+    #[test]
+    fn rules_with_separators_are_rejected() {
+        let mut config = Config::default();
+        config.rename_rules.insert(
+            "Images".to_string(),
+            vec![RenameRule::DatePrefix {
+                format: "%Y/%m/".to_string(),
+            }],
+        );
+        assert!(config.validate_rename_rules().is_err());
+
+        config.rename_rules.insert(
+            "Images".to_string(),
+            vec![RenameRule::DatePrefix {
+                format: DEFAULT_DATE_FORMAT.to_string(),
+            }],
+        );
+        assert!(config.validate_rename_rules().is_ok());
+    }
+
+    #[test]
+    fn subcategories_inherit_parent_rules() {
+        let mut config = Config::default();
+        config.rename_rules.insert(
+            "Documents".to_string(),
+            vec![RenameRule::LowercaseExtension],
+        );
+        assert_eq!(
+            config.rename_rules_for("Documents/Invoices"),
+            &[RenameRule::LowercaseExtension]
+        );
+        assert!(config.rename_rules_for("Images").is_empty());
+    }
+}
@@ -0,0 +1,84 @@
+// ファイル移動のエラーの分類と再試行の間隔
+//
+// 他のプロセスが一時的にファイルを開いている場合（EBUSY、EAGAIN、Windowsの共有違反）は
+// 少し待てば移動できることがありますが、ファイルが無い・権限が無い・容量が足りないといった
+// エラーは何度試しても同じ結果になります。エラーを分類し、一時的なものだけを再試行します。
+
+use std::io::{Error, ErrorKind};
+use std::time::Duration;
+
+/// 最初の再試行までの待機時間
+const BASE_DELAY: Duration = Duration::from_millis(100);
+
+/// 再試行の待機時間の上限
+const MAX_DELAY: Duration = Duration::from_secs(2);
+
+/// EBUSY
+#[cfg(unix)]
+const EBUSY: i32 = 16;
+/// ETXTBSY（実行中のファイル）
+#[cfg(unix)]
+const ETXTBSY: i32 = 26;
+/// EAGAIN
+#[cfg(target_os = "linux")]
+const EAGAIN: i32 = 11;
+#[cfg(all(unix, not(target_os = "linux")))]
+const EAGAIN: i32 = 35;
+
+/// ERROR_SHARING_VIOLATION
+#[cfg(windows)]
+const ERROR_SHARING_VIOLATION: i32 = 32;
+/// ERROR_LOCK_VIOLATION
+#[cfg(windows)]
+const ERROR_LOCK_VIOLATION: i32 = 33;
+
+/// エラーの種類
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ErrorClass {
+    /// 時間をおけば解消する可能性がある（再試行する）
+    Transient,
+    /// 再試行しても解消しない
+    Permanent,
+}
+
+/// エラーを分類（判別できないエラーは再試行しない）
+pub fn classify(err: &Error) -> ErrorClass {
+    if matches!(
+        err.kind(),
+        ErrorKind::Interrupted
+            | ErrorKind::WouldBlock
+            | ErrorKind::TimedOut
+            | ErrorKind::ResourceBusy
+            | ErrorKind::ExecutableFileBusy
+    ) {
+        return ErrorClass::Transient;
+    }
+    match err.raw_os_error() {
+        Some(code) if is_transient_code(code) => ErrorClass::Transient,
+        _ => ErrorClass::Permanent,
+    }
+}
+
+/// 再試行までの待機時間（試行回数に応じて倍増）
+pub fn backoff(attempt: u8) -> Duration {
+    let factor = 1u32 << attempt.saturating_sub(1).min(8);
+    (BASE_DELAY * factor).min(MAX_DELAY)
+}
+
+/// 一時的なエラーのOSのエラーコードか
+#[cfg(unix)]
+fn is_transient_code(code: i32) -> bool {
+    matches!(code, EBUSY | ETXTBSY | EAGAIN)
+}
+
+/// 一時的なエラーのOSのエラーコードか
+#[cfg(windows)]
+fn is_transient_code(code: i32) -> bool {
+    matches!(code, ERROR_SHARING_VIOLATION | ERROR_LOCK_VIOLATION)
+}
+
+/// 一時的なエラーのOSのエラーコードか
+#[cfg(not(any(unix, windows)))]
+fn is_transient_code(_code: i32) -> bool {
+    false
+}
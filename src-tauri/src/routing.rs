@@ -0,0 +1,88 @@
+// 出力先の振り分け
+//
+// カテゴリごとに異なる出力先フォルダ（動画はNAS、書類はホームのDocumentsなど）を
+// 設定できるようにします。出力先の決定は「カテゴリの出力先」「入力元の出力先」
+// 「整理時に指定した出力先」の順に優先します。
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::classify;
+use crate::{AppError, Config, IpcPath, Result};
+
+/// 書き込み確認用の一時ファイル名
+const WRITE_PROBE_FILE_NAME: &str = ".shiwakekun-write-test";
+
+impl Config {
+    /// ファイルを振り分けるカテゴリの出力先を取得
+    ///
+    /// サブカテゴリに出力先がなければ、親カテゴリの出力先を使用します
+    pub fn output_root_for(&self, file: &Path, category: &str, default_output: &Path) -> PathBuf {
+        match classify::ancestors(category).find_map(|name| self.category_outputs.get(name)) {
+            Some(folder) => PathBuf::from(folder),
+            None => self.output_for(file, default_output),
+        }
+    }
+}
+
+/// 整理を始める前に、使用する出力先フォルダが存在し書き込めるかを確認
+///
+/// 整理時に指定した出力先は存在しなければ作成しますが、カテゴリごとの出力先は
+/// 未接続のドライブなどに誤って作成しないよう、存在しない場合はエラーにします。
+/// 問題のある出力先はまとめて報告します
+pub fn validate_output_roots(
+    config: &Config,
+    files: &[IpcPath],
+    default_output: &Path,
+) -> Result<()> {
+    let roots: BTreeSet<PathBuf> = files
+        .iter()
+        .filter_map(|file| {
+            let path = file.as_path();
+            let category = config.category_for(path)?;
+            Some(config.output_root_for(path, &category, default_output))
+        })
+        .collect();
+
+    let mut problems = Vec::new();
+    for root in &roots {
+        if root == default_output && !root.exists() {
+            if let Err(e) = fs::create_dir_all(root) {
+                problems.push(format!(
+                    "{}: フォルダを作成できません ({})",
+                    root.display(),
+                    e
+                ));
+                continue;
+            }
+        }
+
+        if !root.exists() {
+            problems.push(format!("{}: 出力先フォルダが存在しません", root.display()));
+        } else if !root.is_dir() {
+            problems.push(format!(
+                "{}: 出力先がフォルダではありません",
+                root.display()
+            ));
+        } else if let Err(e) = check_writable(root) {
+            problems.push(format!("{}: 書き込みできません ({})", root.display(), e));
+        }
+    }
+
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(AppError::Custom(format!(
+            "出力先フォルダを使用できません:\n{}",
+            problems.join("\n")
+        )))
+    }
+}
+
+/// フォルダに一時ファイルを作成・削除して書き込めるか確認
+fn check_writable(dir: &Path) -> std::io::Result<()> {
+    let probe = dir.join(format!("{}-{}", WRITE_PROBE_FILE_NAME, std::process::id()));
+    fs::File::create(&probe)?;
+    fs::remove_file(&probe)
+}
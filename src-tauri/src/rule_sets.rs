@@ -0,0 +1,230 @@
+// ルールセットの共有
+//
+// 設定のうちカテゴリの振り分けルールだけを独立したファイル（JSON/TOML/YAML）として
+// 書き出し・読み込みできるようにします。チームの共有フォルダに置いたルールセットを
+// 読み込むことで、全員が同じルールで仕分けできます。
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use crate::classify::Subcategory;
+use crate::{extensions, AppError, Config, IpcPath, Result};
+
+/// ルールセットの形式
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RuleSetFormat {
+    Json,
+    Toml,
+    Yaml,
+}
+
+impl RuleSetFormat {
+    /// ファイルの拡張子から形式を判断
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?.to_lowercase();
+        match ext.as_str() {
+            "json" => Some(Self::Json),
+            "toml" => Some(Self::Toml),
+            "yaml" | "yml" => Some(Self::Yaml),
+            _ => None,
+        }
+    }
+}
+
+/// 振り分けルールのセット
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct RuleSet {
+    /// ルールセットの名前
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// ファイル拡張子とカテゴリのマッピング
+    #[serde(default)]
+    pub categories: HashMap<String, Vec<String>>,
+    /// 親カテゴリごとのサブカテゴリのルール
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub subcategories: HashMap<String, Vec<Subcategory>>,
+    /// 拡張子の別名
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub extension_aliases: HashMap<String, String>,
+}
+
+impl RuleSet {
+    /// 設定からルールセットを作成
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            name: None,
+            categories: config.categories.clone(),
+            subcategories: config.subcategories.clone(),
+            extension_aliases: config.extension_aliases.clone(),
+        }
+    }
+}
+
+/// 読み込み時の反映方法
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportMode {
+    /// 既存のルールに追加する（競合したルールは既存のものを残す）
+    Merge,
+    /// 既存のルールを置き換える
+    Replace,
+}
+
+/// ルールの競合
+#[derive(Serialize, Clone, Debug)]
+pub struct RuleConflict {
+    /// 競合した拡張子
+    pub extension: String,
+    /// 既存のルールでのカテゴリ
+    pub existing_category: String,
+    /// 読み込んだルールでのカテゴリ
+    pub imported_category: String,
+}
+
+/// 共有フォルダにあるルールセット
+#[derive(Serialize, Clone, Debug)]
+pub struct SharedRuleSet {
+    /// ファイルのパス
+    pub path: IpcPath,
+    /// ルールセットの名前（未設定の場合はファイル名）
+    pub name: String,
+    /// 読み込めた場合のルールセット
+    pub rule_set: Option<RuleSet>,
+    /// 読み込めなかった場合のエラー
+    pub error: Option<String>,
+}
+
+/// ルールセットをファイルに書き出す（形式は拡張子で判断）
+pub fn export(rule_set: &RuleSet, path: &Path) -> Result<()> {
+    let contents = match format_of(path)? {
+        RuleSetFormat::Json => serde_json::to_string_pretty(rule_set)?,
+        RuleSetFormat::Toml => toml::to_string_pretty(rule_set)
+            .map_err(|e| AppError::Custom(format!("TOMLに変換できません: {}", e)))?,
+        RuleSetFormat::Yaml => serde_yaml::to_string(rule_set)
+            .map_err(|e| AppError::Custom(format!("YAMLに変換できません: {}", e)))?,
+    };
+    fs::write(path, contents)?;
+    Ok(())
+}
+
+/// ルールセットをファイルから読み込む（形式は拡張子で判断）
+pub fn read(path: &Path) -> Result<RuleSet> {
+    let contents = fs::read_to_string(path)?;
+    let rule_set = match format_of(path)? {
+        RuleSetFormat::Json => serde_json::from_str(&contents)?,
+        RuleSetFormat::Toml => toml::from_str(&contents)
+            .map_err(|e| AppError::Custom(format!("TOMLを読み込めません: {}", e)))?,
+        RuleSetFormat::Yaml => serde_yaml::from_str(&contents)
+            .map_err(|e| AppError::Custom(format!("YAMLを読み込めません: {}", e)))?,
+    };
+    Ok(rule_set)
+}
+
+/// 読み込んだルールセットを設定に反映し、競合したルールを返す
+///
+/// 追加の場合、同じカテゴリの拡張子はまとめ、既存のルールで別のカテゴリに
+/// 割り当てられている拡張子は既存のカテゴリのまま残して競合として報告します。
+/// サブカテゴリと拡張子の別名は既存にないものだけを追加します
+pub fn apply(config: &mut Config, rule_set: RuleSet, mode: ImportMode) -> Vec<RuleConflict> {
+    if mode == ImportMode::Replace {
+        config.categories = rule_set.categories;
+        config.subcategories = rule_set.subcategories;
+        config.extension_aliases = rule_set.extension_aliases;
+        return Vec::new();
+    }
+
+    for (alias, target) in rule_set.extension_aliases {
+        config.extension_aliases.entry(alias).or_insert(target);
+    }
+
+    for (parent, children) in rule_set.subcategories {
+        let existing = config.subcategories.entry(parent).or_default();
+        for child in children {
+            if !existing.iter().any(|e| e.name == child.name) {
+                existing.push(child);
+            }
+        }
+    }
+
+    let mut conflicts = Vec::new();
+    let mut imported: Vec<(String, Vec<String>)> = rule_set.categories.into_iter().collect();
+    imported.sort_by(|a, b| a.0.cmp(&b.0));
+
+    for (category, extensions) in imported {
+        for extension in extensions {
+            // 別名や表記の違い（".JPG"と"jpg"など）は同じ拡張子として比較
+            let key = extensions::canonical(&extension, &config.extension_aliases);
+            let existing = config.categories.iter().find(|(_, exts)| {
+                exts.iter()
+                    .any(|e| extensions::canonical(e, &config.extension_aliases) == key)
+            });
+            match existing {
+                Some((existing_category, _)) if existing_category == &category => {}
+                Some((existing_category, _)) => conflicts.push(RuleConflict {
+                    extension,
+                    existing_category: existing_category.clone(),
+                    imported_category: category.clone(),
+                }),
+                None => config
+                    .categories
+                    .entry(category.clone())
+                    .or_default()
+                    .push(extension),
+            }
+        }
+    }
+
+    conflicts
+}
+
+/// 共有フォルダにあるルールセットを一覧
+///
+/// 読み込めなかったファイルもエラーと合わせて一覧に含めます
+pub fn list_shared(dir: &Path) -> Result<Vec<SharedRuleSet>> {
+    if !dir.is_dir() {
+        return Err(AppError::Custom(format!(
+            "ルールセットのフォルダが見つかりません: {}",
+            dir.display()
+        )));
+    }
+
+    let mut paths: Vec<_> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file() && RuleSetFormat::from_path(path).is_some())
+        .collect();
+    paths.sort();
+
+    Ok(paths
+        .into_iter()
+        .map(|path| {
+            let file_name = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            match read(&path) {
+                Ok(rule_set) => SharedRuleSet {
+                    name: rule_set.name.clone().unwrap_or(file_name),
+                    path: IpcPath::from(path),
+                    rule_set: Some(rule_set),
+                    error: None,
+                },
+                Err(e) => SharedRuleSet {
+                    name: file_name,
+                    path: IpcPath::from(path),
+                    rule_set: None,
+                    error: Some(e.to_string()),
+                },
+            }
+        })
+        .collect())
+}
+
+fn format_of(path: &Path) -> Result<RuleSetFormat> {
+    RuleSetFormat::from_path(path).ok_or_else(|| {
+        AppError::Custom(
+            "対応していないファイル形式です（.json / .toml / .yaml のいずれか）".to_string(),
+        )
+    })
+}
@@ -0,0 +1,452 @@
+// 移動先のファイルシステムに合わせたファイル名の確認
+//
+// FAT32・exFATのUSBドライブやSMBの共有フォルダでは、`:`や`?`、末尾のピリオド、
+// Windowsの予約名（`CON`、`NUL`など）を含む名前を作れません。移動先のファイルシステムを
+// 判定し、使えない名前は移動前に置き換えるか、理由を添えて移動しないようにします。
+// ファイル名とパス全体の長さの上限も確認します。
+
+use serde::{Deserialize, Serialize};
+use std::ffi::{OsStr, OsString};
+use std::path::Path;
+
+/// ファイル名の既定の置き換え文字
+const DEFAULT_REPLACEMENT: &str = "_";
+
+/// Windows系のファイルシステムで使えない文字
+const WINDOWS_INVALID_CHARS: &[char] = &['<', '>', ':', '"', '/', '\\', '|', '?', '*'];
+
+/// Windowsの予約名（拡張子が付いていても使えない）
+const WINDOWS_RESERVED_NAMES: &[&str] = &[
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+    "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// Windows系の制限があるファイルシステムの種類
+const WINDOWS_FILE_SYSTEMS: &[&str] = &[
+    "vfat", "msdos", "msdosfs", "fat", "fat32", "exfat", "ntfs", "ntfs3", "fuseblk", "cifs", "smb",
+    "smb2", "smb3", "smbfs",
+];
+
+/// ファイル名の長さの上限
+const MAX_NAME_LEN: usize = 255;
+
+/// パス全体の長さの上限
+#[cfg(windows)]
+const MAX_PATH_LEN: usize = 260;
+#[cfg(not(windows))]
+const MAX_PATH_LEN: usize = 4096;
+
+/// 長さの単位（Windowsでは文字数、それ以外はバイト数で数える）
+#[cfg(windows)]
+const LENGTH_UNIT: &str = "文字";
+#[cfg(not(windows))]
+const LENGTH_UNIT: &str = "バイト";
+
+/// 使えない名前の扱い
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SanitizeMode {
+    /// 使える名前に置き換えて移動する
+    #[default]
+    Replace,
+    /// 移動せずにエラーとして報告する
+    Reject,
+}
+
+/// 移動先に合わせたファイル名の確認の設定
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct SanitizeOptions {
+    /// 使えない名前の扱い
+    pub mode: SanitizeMode,
+    /// 使えない文字の置き換え先
+    pub replacement: String,
+}
+
+impl Default for SanitizeOptions {
+    fn default() -> Self {
+        Self {
+            mode: SanitizeMode::default(),
+            replacement: DEFAULT_REPLACEMENT.to_string(),
+        }
+    }
+}
+
+/// 移動先のファイルシステム
+#[derive(Clone, Debug)]
+pub struct FileSystem {
+    /// ファイルシステムの種類（判定できない場合はNone）
+    pub name: Option<String>,
+    /// Windows系の名前の制限があるか
+    pub windows_rules: bool,
+}
+
+impl FileSystem {
+    /// フォルダのあるファイルシステムを判定
+    pub fn detect(dir: &Path) -> Self {
+        let name = file_system_name(dir);
+        let windows_rules = cfg!(windows)
+            || name
+                .as_deref()
+                .is_some_and(|name| WINDOWS_FILE_SYSTEMS.contains(&name.to_lowercase().as_str()));
+        Self {
+            name,
+            windows_rules,
+        }
+    }
+
+    /// メッセージ用の名前
+    fn label(&self) -> &str {
+        self.name.as_deref().unwrap_or("移動先")
+    }
+}
+
+impl SanitizeOptions {
+    /// 移動先で使える名前を取得
+    ///
+    /// 置き換えた場合はその理由も返します。置き換えない設定で使えない名前の場合は
+    /// 理由をエラーとして返します
+    pub fn sanitize(
+        &self,
+        name: &OsStr,
+        fs: &FileSystem,
+    ) -> std::result::Result<(OsString, Vec<String>), String> {
+        let Some(utf8) = name.to_str() else {
+            // UTF-8として解釈できない名前は長さのみ確認する
+            if name.len() > MAX_NAME_LEN {
+                return Err(format!(
+                    "ファイル名が長すぎます（{}バイト、上限{}バイト）",
+                    name.len(),
+                    MAX_NAME_LEN
+                ));
+            }
+            return Ok((name.to_os_string(), vec![]));
+        };
+
+        let replacement = self.replacement();
+        let mut reasons = Vec::new();
+        let mut sanitized = utf8.to_string();
+        if fs.windows_rules {
+            sanitized = replace_invalid_chars(&sanitized, &replacement, &mut reasons);
+            sanitized = trim_trailing(&sanitized, &mut reasons);
+            sanitized = avoid_reserved(&sanitized, &replacement, &mut reasons);
+        }
+        sanitized = truncate(&sanitized, &mut reasons);
+
+        if reasons.is_empty() {
+            return Ok((name.to_os_string(), reasons));
+        }
+        let reasons: Vec<String> = reasons
+            .into_iter()
+            .map(|reason| format!("{}: {}", fs.label(), reason))
+            .collect();
+        match self.mode {
+            SanitizeMode::Replace if !sanitized.is_empty() => {
+                Ok((OsString::from(sanitized), reasons))
+            }
+            _ => Err(format!("使用できない名前です（{}）", reasons.join("、"))),
+        }
+    }
+
+    /// 置き換え先の文字（使えない文字を含む場合は既定の文字）
+    fn replacement(&self) -> String {
+        let valid = !self.replacement.is_empty()
+            && !self
+                .replacement
+                .chars()
+                .any(|c| WINDOWS_INVALID_CHARS.contains(&c) || c.is_control());
+        if valid {
+            self.replacement.clone()
+        } else {
+            DEFAULT_REPLACEMENT.to_string()
+        }
+    }
+}
+
+/// 連番を付けた後のファイル名とパス全体の長さを確認
+pub fn check_path_length(path: &Path) -> std::result::Result<(), String> {
+    let name = path.file_name().unwrap_or_default();
+    let len = match name.to_str() {
+        Some(name) => name_len(name),
+        None => name.len(),
+    };
+    if len > MAX_NAME_LEN {
+        return Err(format!(
+            "連番を付けるとファイル名が長すぎます（{}{}、上限{}{}）",
+            len, LENGTH_UNIT, MAX_NAME_LEN, LENGTH_UNIT
+        ));
+    }
+
+    let len = path_len(path);
+    if len > MAX_PATH_LEN {
+        return Err(format!(
+            "移動先のパスが長すぎます（{}{}、上限{}{}）",
+            len, LENGTH_UNIT, MAX_PATH_LEN, LENGTH_UNIT
+        ));
+    }
+    Ok(())
+}
+
+/// 使えない文字と制御文字を置き換える
+fn replace_invalid_chars(name: &str, replacement: &str, reasons: &mut Vec<String>) -> String {
+    let mut invalid: Vec<char> = name
+        .chars()
+        .filter(|c| WINDOWS_INVALID_CHARS.contains(c) || c.is_control())
+        .collect();
+    if invalid.is_empty() {
+        return name.to_string();
+    }
+    invalid.dedup();
+    let shown: Vec<String> = invalid
+        .iter()
+        .filter(|c| !c.is_control())
+        .map(|c| format!("「{}」", c))
+        .collect();
+    reasons.push(if shown.is_empty() {
+        "制御文字は使用できません".to_string()
+    } else {
+        format!("{}は使用できません", shown.join(""))
+    });
+    name.chars()
+        .map(|c| {
+            if WINDOWS_INVALID_CHARS.contains(&c) || c.is_control() {
+                replacement.to_string()
+            } else {
+                c.to_string()
+            }
+        })
+        .collect()
+}
+
+/// 末尾のピリオドと空白を取り除く
+fn trim_trailing(name: &str, reasons: &mut Vec<String>) -> String {
+    let trimmed = name.trim_end_matches(['.', ' ']);
+    if trimmed.len() != name.len() {
+        reasons.push("末尾のピリオドや空白は使用できません".to_string());
+    }
+    trimmed.to_string()
+}
+
+/// 予約名の場合は先頭に置き換え文字を付ける（`CON.txt` → `_CON.txt`）
+fn avoid_reserved(name: &str, replacement: &str, reasons: &mut Vec<String>) -> String {
+    let base = name.split('.').next().unwrap_or(name).trim_end();
+    match WINDOWS_RESERVED_NAMES
+        .iter()
+        .find(|reserved| reserved.eq_ignore_ascii_case(base))
+    {
+        Some(reserved) => {
+            reasons.push(format!("予約された名前（{}）は使用できません", reserved));
+            format!("{}{}", replacement, name)
+        }
+        None => name.to_string(),
+    }
+}
+
+/// 長すぎる名前を、拡張子を残して切り詰める
+fn truncate(name: &str, reasons: &mut Vec<String>) -> String {
+    let len = name_len;
+    if len(name) <= MAX_NAME_LEN {
+        return name.to_string();
+    }
+    reasons.push(format!(
+        "ファイル名が長すぎます（{}{}、上限{}{}）",
+        len(name),
+        LENGTH_UNIT,
+        MAX_NAME_LEN,
+        LENGTH_UNIT
+    ));
+
+    let (stem, ext) = match name.rfind('.') {
+        Some(index) if index > 0 && len(&name[index..]) < MAX_NAME_LEN => name.split_at(index),
+        _ => (name, ""),
+    };
+    let mut stem = stem.to_string();
+    while !stem.is_empty() && len(&stem) + len(ext) > MAX_NAME_LEN {
+        stem.pop();
+    }
+    format!("{}{}", stem, ext)
+}
+
+/// ファイル名の長さ（Windowsでは文字数、それ以外はバイト数）
+fn name_len(name: &str) -> usize {
+    if cfg!(windows) {
+        name.encode_utf16().count()
+    } else {
+        name.len()
+    }
+}
+
+/// パスの長さ（Windowsでは文字数、それ以外はバイト数）
+fn path_len(path: &Path) -> usize {
+    if cfg!(windows) {
+        path.to_string_lossy().encode_utf16().count()
+    } else {
+        path.as_os_str().len()
+    }
+}
+
+/// フォルダのあるファイルシステムの種類（`/proc/self/mounts`で最も深いマウント先）
+#[cfg(target_os = "linux")]
+fn file_system_name(dir: &Path) -> Option<String> {
+    let dir = dir.canonicalize().ok()?;
+    let mounts = std::fs::read_to_string("/proc/self/mounts").ok()?;
+    mounts
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let mount_point = unescape_mount_point(fields.nth(1)?);
+            let fs_type = fields.next()?;
+            dir.starts_with(&mount_point)
+                .then(|| (mount_point.len(), fs_type.to_string()))
+        })
+        .max_by_key(|(len, _)| *len)
+        .map(|(_, fs_type)| fs_type)
+}
+
+/// `/proc/self/mounts`のエスケープ（空白は`\040`など）を戻す
+#[cfg(target_os = "linux")]
+fn unescape_mount_point(field: &str) -> String {
+    let mut result = String::new();
+    let mut chars = field.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        let digits: String = chars.by_ref().take(3).collect();
+        match u8::from_str_radix(&digits, 8) {
+            Ok(byte) => result.push(byte as char),
+            Err(_) => {
+                result.push(c);
+                result.push_str(&digits);
+            }
+        }
+    }
+    result
+}
+
+/// フォルダのあるファイルシステムの種類（`statfs`の`f_fstypename`）
+#[cfg(target_os = "macos")]
+fn file_system_name(dir: &Path) -> Option<String> {
+    use std::ffi::{CStr, CString};
+    use std::os::unix::ffi::OsStrExt;
+
+    let path = CString::new(dir.as_os_str().as_bytes()).ok()?;
+    let mut stat: libc::statfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statfs(path.as_ptr(), &mut stat) } != 0 {
+        return None;
+    }
+    let name = unsafe { CStr::from_ptr(stat.f_fstypename.as_ptr()) };
+    Some(name.to_string_lossy().into_owned())
+}
+
+/// フォルダのあるファイルシステムの種類（判定できない環境ではNone）
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn file_system_name(_dir: &Path) -> Option<String> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn windows_fs() -> FileSystem {
+        FileSystem {
+            name: Some("exfat".to_string()),
+            windows_rules: true,
+        }
+    }
+
+    fn sanitize(options: &SanitizeOptions, name: &str) -> std::result::Result<String, String> {
+        options
+            .sanitize(OsStr::new(name), &windows_fs())
+            .map(|(name, _)| name.to_string_lossy().into_owned())
+    }
+
+    #[test]
+    fn invalid_characters_are_replaced() {
+        let options = SanitizeOptions::default();
+        assert_eq!(sanitize(&options, "a:b?.txt").unwrap(), "a_b_.txt");
+
+        let options = SanitizeOptions {
+            replacement: "-".to_string(),
+            ..SanitizeOptions::default()
+        };
+        assert_eq!(sanitize(&options, "a:b.txt").unwrap(), "a-b.txt");
+
+        // 置き換え先自体が使えない文字の場合は既定の文字を使う
+        let options = SanitizeOptions {
+            replacement: "?".to_string(),
+            ..SanitizeOptions::default()
+        };
+        assert_eq!(sanitize(&options, "a:b.txt").unwrap(), "a_b.txt");
+    }
+
+    #[test]
+    fn reserved_names_and_trailing_dots_are_fixed() {
+        let options = SanitizeOptions::default();
+        assert_eq!(sanitize(&options, "CON.txt").unwrap(), "_CON.txt");
+        assert_eq!(sanitize(&options, "nul").unwrap(), "_nul");
+        assert_eq!(sanitize(&options, "report. ").unwrap(), "report");
+        assert_eq!(sanitize(&options, "CONSOLE.txt").unwrap(), "CONSOLE.txt");
+    }
+
+    #[test]
+    fn reject_mode_reports_instead_of_replacing() {
+        let options = SanitizeOptions {
+            mode: SanitizeMode::Reject,
+            ..SanitizeOptions::default()
+        };
+        assert!(sanitize(&options, "a:b.txt").is_err());
+        assert_eq!(sanitize(&options, "ab.txt").unwrap(), "ab.txt");
+    }
+
+    #[test]
+    fn names_without_a_result_are_rejected() {
+        let options = SanitizeOptions::default();
+        assert!(sanitize(&options, "...").is_err());
+    }
+
+    #[test]
+    fn windows_rules_apply_only_to_windows_file_systems() {
+        let options = SanitizeOptions::default();
+        let fs = FileSystem {
+            name: Some("ext4".to_string()),
+            windows_rules: false,
+        };
+        let (name, reasons) = options.sanitize(OsStr::new("a:b.txt"), &fs).unwrap();
+        assert_eq!(name, "a:b.txt");
+        assert!(reasons.is_empty());
+    }
+
+    #[test]
+    fn long_names_are_truncated_keeping_the_extension() {
+        let options = SanitizeOptions::default();
+        let name = format!("{}.txt", "a".repeat(300));
+        let sanitized = sanitize(&options, &name).unwrap();
+        assert_eq!(sanitized.len(), MAX_NAME_LEN);
+        assert!(sanitized.ends_with(".txt"));
+    }
+
+    #[test]
+    fn numbered_names_over_the_limit_are_rejected() {
+        let dir = PathBuf::from("out");
+        let name = format!("{}.txt", "a".repeat(MAX_NAME_LEN - 4));
+        assert!(check_path_length(&dir.join(&name)).is_ok());
+
+        let numbered = format!("{} (1).txt", "a".repeat(MAX_NAME_LEN - 4));
+        let err = check_path_length(&dir.join(numbered)).unwrap_err();
+        assert!(err.contains("ファイル名が長すぎます"), "{err}");
+    }
+
+    #[test]
+    fn long_paths_are_rejected() {
+        let mut path = PathBuf::from("out");
+        while path_len(&path) <= MAX_PATH_LEN {
+            path.push("a".repeat(100));
+        }
+        let err = check_path_length(&path).unwrap_err();
+        assert!(err.contains("パスが長すぎます"), "{err}");
+    }
+}
@@ -0,0 +1,649 @@
+// 入力フォルダのスキャン
+//
+// 入力フォルダからファイルを収集します。設定されたinclude/excludeパターン、
+// 組み込みのシステムファイル除外リスト、スキャン対象フォルダ内の`.shiwakeignore`
+// （gitignoreと同じ書式）に従って対象外のファイルとフォルダを除外します。
+
+use glob::{MatchOptions, Pattern};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs::{self, Metadata};
+#[cfg(unix)]
+use std::os::unix::fs::MetadataExt;
+#[cfg(windows)]
+use std::os::windows::fs::MetadataExt;
+use std::path::{Component, Path, PathBuf};
+
+use crate::{is_in_category_folder, AppError, IpcPath, Result};
+
+/// スキャン対象フォルダに置く除外設定ファイル名
+pub const IGNORE_FILE_NAME: &str = ".shiwakeignore";
+
+/// 組み込みで除外するシステムファイル・一時ファイルのパターン
+const BUILTIN_FILE_EXCLUDES: &[&str] = &[
+    ".DS_Store",
+    "._*",
+    "Icon\r",
+    "Thumbs.db",
+    "ehthumbs.db",
+    "desktop.ini",
+    "~$*",
+    "*.crdownload",
+    "*.part",
+    "*.partial",
+    "*.download",
+    "*.opdownload",
+    IGNORE_FILE_NAME,
+];
+
+/// 組み込みで除外するフォルダのパターン
+const BUILTIN_DIR_EXCLUDES: &[&str] = &[
+    ".git",
+    ".svn",
+    ".hg",
+    ".Trash",
+    ".Trashes",
+    ".Spotlight-V100",
+    ".fseventsd",
+    "$RECYCLE.BIN",
+    "System Volume Information",
+];
+
+/// スキャン設定
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct ScanOptions {
+    /// 対象とするファイルのパターン（空の場合は全て対象）
+    pub include_patterns: Vec<String>,
+    /// 除外するファイル・フォルダのパターン
+    pub exclude_patterns: Vec<String>,
+    /// 組み込みのシステムファイル除外リストを使用するか
+    pub builtin_excludes: bool,
+    /// `.shiwakeignore`ファイルを読み込むか
+    pub use_ignore_files: bool,
+    /// 再帰時にたどるサブフォルダの最大階層（Noneは無制限、0は入力フォルダ直下のみ）
+    pub max_depth: Option<usize>,
+    /// シンボリックリンクを対象にするか（falseの場合はリンクを警告として報告して無視する）
+    ///
+    /// ファイルへのリンクはリンク先ではなくリンク自体を移動します
+    pub follow_symlinks: bool,
+    /// 隠しファイル・隠しフォルダを含めるか
+    pub include_hidden: bool,
+    /// 入力フォルダと異なるファイルシステムには降りない（`find -xdev`相当）
+    pub same_file_system: bool,
+}
+
+impl Default for ScanOptions {
+    fn default() -> Self {
+        Self {
+            include_patterns: Vec::new(),
+            exclude_patterns: Vec::new(),
+            builtin_excludes: true,
+            use_ignore_files: true,
+            max_depth: None,
+            follow_symlinks: false,
+            include_hidden: false,
+            same_file_system: false,
+        }
+    }
+}
+
+/// 設定パターンの照合オプション（大文字小文字を区別しない）
+const CONFIG_MATCH_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: false,
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
+
+/// `.shiwakeignore`の照合オプション（gitignoreと同様に大文字小文字を区別する）
+const IGNORE_MATCH_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
+
+/// フォルダを一意に識別するキー（ループ検出用）
+#[cfg(unix)]
+type DirKey = (u64, u64);
+#[cfg(not(unix))]
+type DirKey = PathBuf;
+
+/// フォルダのキーを取得
+///
+/// Unix系ではデバイス番号とiノード番号、それ以外では正規化したパスを使用します
+#[cfg(unix)]
+fn dir_key(_path: &Path, metadata: &Metadata) -> Option<DirKey> {
+    Some((metadata.dev(), metadata.ino()))
+}
+
+#[cfg(not(unix))]
+fn dir_key(path: &Path, _metadata: &Metadata) -> Option<DirKey> {
+    fs::canonicalize(path).ok()
+}
+
+/// ファイルシステムの識別子を取得（取得できない環境ではNone）
+#[cfg(unix)]
+fn device_id(metadata: &Metadata) -> Option<u64> {
+    Some(metadata.dev())
+}
+
+#[cfg(not(unix))]
+fn device_id(_metadata: &Metadata) -> Option<u64> {
+    None
+}
+
+/// 隠しファイルかどうか
+///
+/// ドットで始まる名前に加え、Windowsでは隠し属性も確認します
+fn is_hidden(name: &str, _metadata: &Metadata) -> bool {
+    #[cfg(windows)]
+    {
+        const FILE_ATTRIBUTE_HIDDEN: u32 = 0x2;
+        if _metadata.file_attributes() & FILE_ATTRIBUTE_HIDDEN != 0 {
+            return true;
+        }
+    }
+    name.starts_with('.')
+}
+
+/// パスを`/`区切りの文字列に変換
+fn to_slash_path(path: &Path) -> String {
+    path.components()
+        .filter_map(|component| match component {
+            Component::Normal(name) => Some(name.to_string_lossy()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// パターンの一覧をコンパイル
+fn compile_patterns(patterns: &[String]) -> Result<Vec<Pattern>> {
+    patterns
+        .iter()
+        .map(|pattern| {
+            Pattern::new(pattern)
+                .map_err(|e| AppError::Custom(format!("無効なパターンです: {} ({})", pattern, e)))
+        })
+        .collect()
+}
+
+/// パターンがファイル名またはスキャンルートからの相対パスに一致するか
+///
+/// `/`を含まないパターンはファイル名のみと照合します
+fn matches_any(patterns: &[Pattern], name: &str, rel_path: &str) -> bool {
+    patterns.iter().any(|pattern| {
+        let target = if pattern.as_str().contains('/') {
+            rel_path
+        } else {
+            name
+        };
+        pattern.matches_with(target, CONFIG_MATCH_OPTIONS)
+    })
+}
+
+/// `.shiwakeignore`の1行分のルール
+struct IgnoreRule {
+    pattern: Pattern,
+    /// `!`で始まる再包含ルールか
+    negated: bool,
+    /// 末尾が`/`でフォルダのみに適用されるか
+    dir_only: bool,
+    /// `/`を含み、ignoreファイルのあるフォルダからの相対パスと照合するか
+    anchored: bool,
+}
+
+/// 1つの`.shiwakeignore`ファイルの内容
+struct IgnoreFile {
+    /// ignoreファイルが置かれているフォルダ
+    base: PathBuf,
+    rules: Vec<IgnoreRule>,
+}
+
+impl IgnoreFile {
+    /// フォルダ内の`.shiwakeignore`を読み込む（存在しない場合はNone）
+    fn load(dir: &Path) -> Option<Self> {
+        let contents = fs::read_to_string(dir.join(IGNORE_FILE_NAME)).ok()?;
+        let rules = contents.lines().filter_map(Self::parse_line).collect();
+        Some(Self {
+            base: dir.to_path_buf(),
+            rules,
+        })
+    }
+
+    /// gitignoreの書式で1行を解釈
+    fn parse_line(line: &str) -> Option<IgnoreRule> {
+        let line = line.trim_end();
+        if line.is_empty() || line.starts_with('#') {
+            return None;
+        }
+
+        let (negated, line) = match line.strip_prefix('!') {
+            Some(rest) => (true, rest),
+            None => (false, line.strip_prefix('\\').unwrap_or(line)),
+        };
+
+        let (dir_only, line) = match line.strip_suffix('/') {
+            Some(rest) => (true, rest),
+            None => (false, line),
+        };
+
+        let anchored = line.contains('/');
+        let line = line.strip_prefix('/').unwrap_or(line);
+        if line.is_empty() {
+            return None;
+        }
+
+        Pattern::new(line).ok().map(|pattern| IgnoreRule {
+            pattern,
+            negated,
+            dir_only,
+            anchored,
+        })
+    }
+
+    /// このファイルのルールで判定（一致するルールがなければNone）
+    ///
+    /// 後に書かれたルールほど優先されます
+    fn decide(&self, path: &Path, is_dir: bool) -> Option<bool> {
+        let rel_path = to_slash_path(path.strip_prefix(&self.base).ok()?);
+        let name = path.file_name()?.to_string_lossy();
+
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| {
+                if rule.dir_only && !is_dir {
+                    return false;
+                }
+                let target = if rule.anchored {
+                    rel_path.as_str()
+                } else {
+                    &name
+                };
+                rule.pattern.matches_with(target, IGNORE_MATCH_OPTIONS)
+            })
+            .map(|rule| !rule.negated)
+    }
+}
+
+/// スキャン中に発生した警告（読み取れなかったフォルダなど）
+#[derive(Serialize, Clone, Debug)]
+pub struct ScanWarning {
+    /// 対象のパス
+    pub path: IpcPath,
+    /// 警告の内容
+    pub message: String,
+}
+
+/// スキャン結果の受け取り先
+pub trait ScanSink {
+    /// 収集対象のファイルが見つかった
+    fn file(&mut self, path: PathBuf);
+
+    /// フォルダの走査を開始した
+    fn dir_visited(&mut self, _dir: &Path) {}
+
+    /// 走査を続けられる問題が発生した
+    fn warning(&mut self, warning: ScanWarning);
+
+    /// スキャンを中断するか
+    fn is_cancelled(&self) -> bool {
+        false
+    }
+}
+
+/// ファイルと警告を収集するだけの受け取り先
+#[derive(Default)]
+pub struct CollectSink {
+    pub files: Vec<PathBuf>,
+    pub warnings: Vec<ScanWarning>,
+}
+
+impl ScanSink for CollectSink {
+    fn file(&mut self, path: PathBuf) {
+        self.files.push(path);
+    }
+
+    fn warning(&mut self, warning: ScanWarning) {
+        self.warnings.push(warning);
+    }
+}
+
+/// スキャン時の除外判定
+struct ScanFilter {
+    include: Vec<Pattern>,
+    exclude: Vec<Pattern>,
+    builtin_files: Vec<Pattern>,
+    builtin_dirs: Vec<Pattern>,
+}
+
+impl ScanFilter {
+    /// スキャン設定から除外判定を作成
+    fn new(options: &ScanOptions) -> Result<Self> {
+        let builtin = |patterns: &[&str]| -> Vec<Pattern> {
+            if options.builtin_excludes {
+                patterns
+                    .iter()
+                    .filter_map(|p| Pattern::new(p).ok())
+                    .collect()
+            } else {
+                Vec::new()
+            }
+        };
+
+        Ok(Self {
+            include: compile_patterns(&options.include_patterns)?,
+            exclude: compile_patterns(&options.exclude_patterns)?,
+            builtin_files: builtin(BUILTIN_FILE_EXCLUDES),
+            builtin_dirs: builtin(BUILTIN_DIR_EXCLUDES),
+        })
+    }
+
+    /// ファイルを収集対象とするか
+    fn accepts_file(&self, name: &str, rel_path: &str) -> bool {
+        let builtin_match = self
+            .builtin_files
+            .iter()
+            .any(|pattern| pattern.matches_with(name, CONFIG_MATCH_OPTIONS));
+        if builtin_match || matches_any(&self.exclude, name, rel_path) {
+            return false;
+        }
+        self.include.is_empty() || matches_any(&self.include, name, rel_path)
+    }
+
+    /// フォルダの中をスキャンするか
+    fn accepts_dir(&self, name: &str, rel_path: &str) -> bool {
+        let builtin_match = self
+            .builtin_dirs
+            .iter()
+            .any(|pattern| pattern.matches_with(name, CONFIG_MATCH_OPTIONS));
+        !builtin_match && !matches_any(&self.exclude, name, rel_path)
+    }
+}
+
+/// フォルダを走査してファイルを収集する
+pub struct Scanner<'a> {
+    root: PathBuf,
+    options: &'a ScanOptions,
+    filter: ScanFilter,
+    /// たどるサブフォルダの最大階層
+    max_depth: Option<usize>,
+    output_folders: &'a [PathBuf],
+    category_names: &'a [String],
+    /// 現在のフォルダまでに読み込んだ`.shiwakeignore`（浅い順）
+    ignore_stack: Vec<IgnoreFile>,
+    /// 訪問済みフォルダ（シンボリックリンクのループ検出用）
+    visited: HashSet<DirKey>,
+    /// 入力フォルダのファイルシステム
+    root_device: Option<u64>,
+}
+
+impl<'a> Scanner<'a> {
+    /// 新しいスキャナーを作成
+    ///
+    /// `recursive`がfalseの場合は`max_depth`の設定にかかわらず入力フォルダ直下のみを対象とします
+    pub fn new(
+        root: &Path,
+        recursive: bool,
+        options: &'a ScanOptions,
+        output_folders: &'a [PathBuf],
+        category_names: &'a [String],
+    ) -> Result<Self> {
+        let root_metadata = fs::metadata(root)?;
+        let mut visited = HashSet::new();
+        visited.extend(dir_key(root, &root_metadata));
+
+        Ok(Self {
+            root: root.to_path_buf(),
+            options,
+            filter: ScanFilter::new(options)?,
+            max_depth: if recursive {
+                options.max_depth
+            } else {
+                Some(0)
+            },
+            output_folders,
+            category_names,
+            ignore_stack: Vec::new(),
+            visited,
+            root_device: device_id(&root_metadata),
+        })
+    }
+
+    /// スキャンを実行し、見つかったファイルを`sink`に渡す
+    ///
+    /// 入力フォルダ自体を読み取れない場合はエラーになりますが、サブフォルダや
+    /// 個々のエントリを読み取れない場合は警告として報告して走査を続けます
+    pub fn run(mut self, sink: &mut dyn ScanSink) -> Result<()> {
+        let root = self.root.clone();
+        let entries = fs::read_dir(&root)?;
+        self.scan_dir(&root, entries, 0, sink);
+        Ok(())
+    }
+
+    /// `.shiwakeignore`のルールで除外されるか
+    ///
+    /// 深い階層のignoreファイルほど優先されます
+    fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        self.ignore_stack
+            .iter()
+            .rev()
+            .find_map(|ignore| ignore.decide(path, is_dir))
+            .unwrap_or(false)
+    }
+
+    /// 出力先フォルダの下位階層にあるカテゴリフォルダ内か
+    fn is_in_output_category(&self, path: &Path) -> bool {
+        self.output_folders.iter().any(|output_folder| {
+            path.starts_with(output_folder)
+                && is_in_category_folder(path, output_folder, self.category_names)
+        })
+    }
+
+    /// サブフォルダに降りるかどうかを判定
+    ///
+    /// 階層制限、ファイルシステム境界、シンボリックリンクのループを確認し、
+    /// 降りる場合は訪問済みとして記録します
+    fn should_descend(&mut self, path: &Path, metadata: &Metadata, depth: usize) -> bool {
+        if self.max_depth.is_some_and(|max_depth| depth >= max_depth) {
+            return false;
+        }
+
+        if self.options.same_file_system
+            && self.root_device.is_some()
+            && device_id(metadata) != self.root_device
+        {
+            return false;
+        }
+
+        match dir_key(path, metadata) {
+            Some(key) => self.visited.insert(key),
+            None => true,
+        }
+    }
+
+    /// サブフォルダを走査（読み取れない場合は警告）
+    fn visit_dir(&mut self, dir: &Path, depth: usize, sink: &mut dyn ScanSink) {
+        match fs::read_dir(dir) {
+            Ok(entries) => self.scan_dir(dir, entries, depth, sink),
+            Err(e) => sink.warning(ScanWarning {
+                path: IpcPath::from(dir),
+                message: format!("フォルダを読み取れません: {}", e),
+            }),
+        }
+    }
+
+    fn scan_dir(
+        &mut self,
+        dir: &Path,
+        entries: fs::ReadDir,
+        depth: usize,
+        sink: &mut dyn ScanSink,
+    ) {
+        sink.dir_visited(dir);
+
+        // フォルダ内の`.shiwakeignore`はそのフォルダ以下にのみ適用する
+        let ignore = if self.options.use_ignore_files {
+            IgnoreFile::load(dir)
+        } else {
+            None
+        };
+        let has_ignore = ignore.is_some();
+        self.ignore_stack.extend(ignore);
+
+        for entry in entries {
+            if sink.is_cancelled() {
+                break;
+            }
+            match entry {
+                Ok(entry) => self.scan_entry(entry, depth, sink),
+                Err(e) => sink.warning(ScanWarning {
+                    path: IpcPath::from(dir),
+                    message: format!("フォルダ内の項目を読み取れません: {}", e),
+                }),
+            }
+        }
+
+        if has_ignore {
+            self.ignore_stack.pop();
+        }
+    }
+
+    fn scan_entry(&mut self, entry: fs::DirEntry, depth: usize, sink: &mut dyn ScanSink) {
+        let path = entry.path();
+
+        // 出力先フォルダの下位階層にあるカテゴリフォルダ内のファイルを除外
+        if self.is_in_output_category(&path) {
+            return;
+        }
+
+        let warn = |sink: &mut dyn ScanSink, path: &Path, e: std::io::Error| {
+            sink.warning(ScanWarning {
+                path: IpcPath::from(path),
+                message: format!("情報を取得できません: {}", e),
+            })
+        };
+
+        // シンボリックリンクは対象にする設定の場合のみリンク先の情報で判定する
+        let is_symlink = match entry.file_type() {
+            Ok(file_type) => file_type.is_symlink(),
+            Err(e) => return warn(sink, &path, e),
+        };
+        if is_symlink && !self.options.follow_symlinks {
+            return sink.warning(ScanWarning {
+                path: IpcPath::from(path.as_path()),
+                message: "シンボリックリンクのため対象外にしました".to_string(),
+            });
+        }
+        let metadata = if is_symlink {
+            match fs::metadata(&path) {
+                Ok(metadata) => metadata,
+                // リンク切れのシンボリックリンクは無視する
+                Err(_) => return,
+            }
+        } else {
+            match entry.metadata() {
+                Ok(metadata) => metadata,
+                Err(e) => return warn(sink, &path, e),
+            }
+        };
+
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if !self.options.include_hidden && is_hidden(&name, &metadata) {
+            return;
+        }
+
+        let rel_path = to_slash_path(path.strip_prefix(&self.root).unwrap_or(&path));
+
+        if metadata.is_file() {
+            if !self.filter.accepts_file(&name, &rel_path) || self.is_ignored(&path, false) {
+                return;
+            }
+            // ファイルの場合は収集対象とする（UTF-8として解釈できない名前も含める）
+            sink.file(path);
+        } else if metadata.is_dir() {
+            if !self.filter.accepts_dir(&name, &rel_path) || self.is_ignored(&path, true) {
+                return;
+            }
+            if !self.should_descend(&path, &metadata, depth) {
+                return;
+            }
+            // 条件を満たすサブフォルダは再帰的に収集
+            self.visit_dir(&path, depth + 1, sink);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ignore_file(contents: &str) -> IgnoreFile {
+        IgnoreFile {
+            base: PathBuf::from("/in"),
+            rules: contents
+                .lines()
+                .filter_map(IgnoreFile::parse_line)
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn comments_and_blank_lines_are_skipped() {
+        let ignore = ignore_file("# comment\n\n   \n/\n*.tmp\n");
+        assert_eq!(ignore.rules.len(), 1);
+        assert_eq!(ignore.decide(Path::new("/in/a.tmp"), false), Some(true));
+        assert_eq!(ignore.decide(Path::new("/in/a.txt"), false), None);
+    }
+
+    #[test]
+    fn escaped_hash_is_a_pattern() {
+        let ignore = ignore_file("\\#notes.txt");
+        assert_eq!(
+            ignore.decide(Path::new("/in/#notes.txt"), false),
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn later_negation_re_includes() {
+        let ignore = ignore_file("*.log\n!keep.log");
+        assert_eq!(ignore.decide(Path::new("/in/a.log"), false), Some(true));
+        assert_eq!(ignore.decide(Path::new("/in/keep.log"), false), Some(false));
+
+        // 後に書かれたルールほど優先される
+        let ignore = ignore_file("!keep.log\n*.log");
+        assert_eq!(ignore.decide(Path::new("/in/keep.log"), false), Some(true));
+    }
+
+    #[test]
+    fn trailing_slash_matches_only_folders() {
+        let ignore = ignore_file("build/");
+        assert_eq!(ignore.decide(Path::new("/in/build"), true), Some(true));
+        assert_eq!(ignore.decide(Path::new("/in/build"), false), None);
+    }
+
+    #[test]
+    fn patterns_with_a_slash_are_relative_to_the_ignore_file() {
+        let ignore = ignore_file("/top.txt\ndocs/*.md");
+        assert_eq!(ignore.decide(Path::new("/in/top.txt"), false), Some(true));
+        assert_eq!(ignore.decide(Path::new("/in/sub/top.txt"), false), None);
+        assert_eq!(ignore.decide(Path::new("/in/docs/a.md"), false), Some(true));
+        assert_eq!(ignore.decide(Path::new("/in/docs/sub/a.md"), false), None);
+
+        // スラッシュを含まないパターンはどの階層のファイル名とも照合する
+        let ignore = ignore_file("*.md");
+        assert_eq!(
+            ignore.decide(Path::new("/in/docs/sub/a.md"), false),
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn matching_is_case_sensitive() {
+        let ignore = ignore_file("*.tmp");
+        assert_eq!(ignore.decide(Path::new("/in/a.TMP"), false), None);
+    }
+}
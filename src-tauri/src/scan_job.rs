@@ -0,0 +1,160 @@
+// 入力フォルダの非同期スキャン
+//
+// 別スレッドでスキャンを行い、見つかったファイルを一定件数または一定時間ごとに
+// まとめて`scan-progress`イベントで通知します。実行中のスキャンはジョブIDで中断できます。
+
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::Emitter;
+
+use crate::scan::{ScanSink, ScanWarning};
+use crate::{IpcPath, Result};
+
+/// スキャン進捗イベント名
+const SCAN_EVENT: &str = "scan-progress";
+
+/// 一度の通知に含めるファイル数の上限
+const SCAN_CHUNK_SIZE: usize = 500;
+
+/// 通知の最小間隔（ミリ秒）
+const SCAN_EMIT_INTERVAL_MS: u64 = 100;
+
+/// 実行中のスキャンと中断フラグ
+static ACTIVE_SCANS: Mutex<Vec<(String, Arc<AtomicBool>)>> = Mutex::new(Vec::new());
+
+/// スキャンの進捗状況
+#[derive(Serialize, Clone, Debug)]
+pub struct ScanProgress {
+    /// ジョブID
+    pub job_id: String,
+    /// 前回の通知以降に見つかったファイル
+    pub files: Vec<IpcPath>,
+    /// これまでに見つかったファイル数
+    pub files_found: usize,
+    /// これまでに走査したフォルダ数
+    pub dirs_visited: usize,
+    /// 現在走査中のフォルダ
+    pub current_dir: Option<IpcPath>,
+    /// 前回の通知以降に発生した警告
+    pub warnings: Vec<ScanWarning>,
+    /// スキャンが終了したかどうか
+    pub finished: bool,
+    /// 中断されたかどうか
+    pub cancelled: bool,
+    /// スキャンを開始できなかった場合のエラー
+    pub error: Option<String>,
+}
+
+/// 見つかったファイルをまとめて通知する受け取り先
+struct StreamingSink {
+    window: tauri::Window,
+    job_id: String,
+    cancel_flag: Arc<AtomicBool>,
+    pending_files: Vec<IpcPath>,
+    pending_warnings: Vec<ScanWarning>,
+    files_found: usize,
+    dirs_visited: usize,
+    current_dir: Option<PathBuf>,
+    last_emit: Instant,
+}
+
+impl StreamingSink {
+    /// 溜まっているファイルと警告を通知
+    fn emit(&mut self, finished: bool, error: Option<String>) {
+        let _ = self.window.emit(
+            SCAN_EVENT,
+            ScanProgress {
+                job_id: self.job_id.clone(),
+                files: std::mem::take(&mut self.pending_files),
+                files_found: self.files_found,
+                dirs_visited: self.dirs_visited,
+                current_dir: self.current_dir.as_deref().map(IpcPath::from),
+                warnings: std::mem::take(&mut self.pending_warnings),
+                finished,
+                cancelled: self.cancel_flag.load(Ordering::SeqCst),
+                error,
+            },
+        );
+        self.last_emit = Instant::now();
+    }
+
+    /// 件数または経過時間が閾値を超えていれば通知
+    fn emit_if_due(&mut self) {
+        if self.pending_files.len() >= SCAN_CHUNK_SIZE
+            || self.last_emit.elapsed() >= Duration::from_millis(SCAN_EMIT_INTERVAL_MS)
+        {
+            self.emit(false, None);
+        }
+    }
+}
+
+impl ScanSink for StreamingSink {
+    fn file(&mut self, path: PathBuf) {
+        self.pending_files.push(IpcPath::from(path));
+        self.files_found += 1;
+        self.emit_if_due();
+    }
+
+    fn dir_visited(&mut self, dir: &Path) {
+        self.dirs_visited += 1;
+        self.current_dir = Some(dir.to_path_buf());
+        self.emit_if_due();
+    }
+
+    fn warning(&mut self, warning: ScanWarning) {
+        self.pending_warnings.push(warning);
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancel_flag.load(Ordering::SeqCst)
+    }
+}
+
+/// スキャンジョブを別スレッドで開始
+///
+/// `scan`には結果の受け取り先が渡されます。終了時には`finished`を立てた通知を送ります
+pub fn start<F>(window: tauri::Window, job_id: &str, scan: F)
+where
+    F: FnOnce(&mut dyn ScanSink) -> Result<()> + Send + 'static,
+{
+    let cancel_flag = Arc::new(AtomicBool::new(false));
+    lock_scans().push((job_id.to_string(), cancel_flag.clone()));
+
+    let mut sink = StreamingSink {
+        window,
+        job_id: job_id.to_string(),
+        cancel_flag,
+        pending_files: Vec::new(),
+        pending_warnings: Vec::new(),
+        files_found: 0,
+        dirs_visited: 0,
+        current_dir: None,
+        last_emit: Instant::now(),
+    };
+
+    std::thread::spawn(move || {
+        let error = scan(&mut sink).err().map(|e| e.to_string());
+        sink.current_dir = None;
+        sink.emit(true, error);
+
+        lock_scans().retain(|(id, _)| id != &sink.job_id);
+    });
+}
+
+/// 実行中のスキャンを中断（該当するスキャンがなければfalse）
+pub fn cancel(job_id: &str) -> bool {
+    match lock_scans().iter().find(|(id, _)| id == job_id) {
+        Some((_, flag)) => {
+            flag.store(true, Ordering::SeqCst);
+            true
+        }
+        None => false,
+    }
+}
+
+fn lock_scans() -> std::sync::MutexGuard<'static, Vec<(String, Arc<AtomicBool>)>> {
+    ACTIVE_SCANS.lock().unwrap_or_else(|e| e.into_inner())
+}
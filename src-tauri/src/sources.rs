@@ -0,0 +1,88 @@
+// 入力元フォルダ
+//
+// 複数の入力元フォルダ（ダウンロード、デスクトップ、共有スキャナの受信フォルダなど）を
+// それぞれ個別の設定で扱います。入力元ごとに再帰の有無、出力先フォルダ、
+// カテゴリのルールセット、有効・無効を設定できます。
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::Config;
+
+/// 入力元フォルダの設定
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct InputSource {
+    /// 入力元フォルダのパス（入力元の識別にも使用する）
+    pub path: String,
+    /// サブフォルダも含めてスキャンするか
+    #[serde(default = "default_true")]
+    pub recursive: bool,
+    /// この入力元のファイルの出力先（未設定の場合は整理時に指定した出力先）
+    #[serde(default)]
+    pub output_folder: Option<String>,
+    /// この入力元で使用するカテゴリのルール（未設定の場合は共通のルール）
+    #[serde(default)]
+    pub categories: Option<HashMap<String, Vec<String>>>,
+    /// スキャン対象に含めるか
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl InputSource {
+    /// 既定の設定で入力元を作成
+    pub fn new(path: &str) -> Self {
+        Self {
+            path: path.to_string(),
+            recursive: true,
+            output_folder: None,
+            categories: None,
+            enabled: true,
+        }
+    }
+}
+
+impl Config {
+    /// 有効な入力元の一覧を取得
+    ///
+    /// 従来の`input_folder`が設定されていて入力元一覧に含まれていない場合は、
+    /// 先頭に追加して扱います（再帰の有無は`legacy_recursive`に従う）
+    pub fn input_sources(&self, legacy_recursive: bool) -> Vec<InputSource> {
+        let mut sources = self.input_sources.clone();
+        if let Some(folder) = &self.input_folder {
+            if !sources.iter().any(|source| &source.path == folder) {
+                let mut legacy = InputSource::new(folder);
+                legacy.recursive = legacy_recursive;
+                sources.insert(0, legacy);
+            }
+        }
+        sources
+    }
+
+    /// ファイルが属する入力元を取得（最も深い入力元を優先）
+    pub fn source_for(&self, file: &Path) -> Option<&InputSource> {
+        self.input_sources
+            .iter()
+            .filter(|source| file.starts_with(&source.path))
+            .max_by_key(|source| Path::new(&source.path).components().count())
+    }
+
+    /// ファイルに適用するカテゴリのルールを取得
+    pub fn categories_for(&self, file: &Path) -> &HashMap<String, Vec<String>> {
+        self.source_for(file)
+            .and_then(|source| source.categories.as_ref())
+            .unwrap_or(&self.categories)
+    }
+
+    /// ファイルの出力先を取得（入力元に出力先がなければ`default_output`）
+    pub fn output_for(&self, file: &Path, default_output: &Path) -> PathBuf {
+        self.source_for(file)
+            .and_then(|source| source.output_folder.as_deref())
+            .map(PathBuf::from)
+            .unwrap_or_else(|| default_output.to_path_buf())
+    }
+}
@@ -0,0 +1,182 @@
+// 空き容量の事前確認
+//
+// 異なるデバイスへの移動はコピーになるため、途中で移動先のディスクがいっぱいになることが
+// あります。整理を始める前に移動先のデバイスごとに必要な容量を集計して空き容量と比べ、
+// 処理中に容量不足になった場合は残りのファイルを処理せずに止めます。
+
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+use crate::{classify, Config, IpcPath};
+
+/// 移動先のデバイスごとの必要容量と空き容量
+#[derive(Serialize, Clone, Debug)]
+pub struct DeviceSpace {
+    /// 移動先のフォルダ（デバイス上の代表的なもの）
+    pub path: String,
+    /// コピーが必要なファイル数
+    pub file_count: usize,
+    /// 必要なバイト数
+    pub required_bytes: u64,
+    /// 空いているバイト数（取得できない場合はNone）
+    pub available_bytes: Option<u64>,
+}
+
+impl DeviceSpace {
+    /// 空き容量が足りないか（空き容量が取得できない場合は足りるものとする）
+    pub fn insufficient(&self) -> bool {
+        self.available_bytes
+            .is_some_and(|available| available < self.required_bytes)
+    }
+}
+
+/// 空き容量の確認結果
+#[derive(Serialize, Clone, Debug)]
+pub struct SpaceCheck {
+    /// 移動先のデバイスごとの内訳
+    pub devices: Vec<DeviceSpace>,
+    /// 全てのデバイスで空き容量が足りるか
+    pub sufficient: bool,
+}
+
+impl SpaceCheck {
+    /// 容量が足りない場合のメッセージ
+    pub fn message(&self) -> String {
+        let lines: Vec<String> = self
+            .devices
+            .iter()
+            .filter(|device| device.insufficient())
+            .map(|device| {
+                format!(
+                    "{}: 必要 {} / 空き {}（{}件）",
+                    device.path,
+                    format_bytes(device.required_bytes),
+                    format_bytes(device.available_bytes.unwrap_or(0)),
+                    device.file_count
+                )
+            })
+            .collect();
+        format!("移動先の空き容量が不足しています\n{}", lines.join("\n"))
+    }
+}
+
+/// ファイルの移動に必要な容量を移動先のデバイスごとに集計して、空き容量と比べる
+///
+/// 元のファイルと同じデバイスへの移動は名前の変更で済むため集計しません
+pub fn check(
+    config: &Config,
+    files: &[IpcPath],
+    sizes: &[u64],
+    default_output: &Path,
+) -> SpaceCheck {
+    let mut devices: Vec<(String, DeviceSpace)> = Vec::new();
+    for (file_path, &size) in files.iter().zip(sizes) {
+        let path = file_path.as_path();
+        let Some(category) = config.category_for(path) else {
+            continue;
+        };
+        let output_root = config.output_root_for(path, &category, default_output);
+        let dest_dir = existing_ancestor(&classify::category_dir(&output_root, &category));
+        let Some(dest_device) = device_key(&dest_dir) else {
+            continue;
+        };
+        if device_key(path).as_ref() == Some(&dest_device) {
+            continue;
+        }
+
+        let index = match devices.iter().position(|(key, _)| *key == dest_device) {
+            Some(index) => index,
+            None => {
+                devices.push((
+                    dest_device,
+                    DeviceSpace {
+                        path: output_root.to_string_lossy().into_owned(),
+                        file_count: 0,
+                        required_bytes: 0,
+                        available_bytes: fs2::available_space(&dest_dir).ok(),
+                    },
+                ));
+                devices.len() - 1
+            }
+        };
+        let device = &mut devices[index].1;
+        device.file_count += 1;
+        device.required_bytes += size;
+    }
+
+    let devices: Vec<DeviceSpace> = devices.into_iter().map(|(_, device)| device).collect();
+    let sufficient = !devices.iter().any(DeviceSpace::insufficient);
+    SpaceCheck {
+        devices,
+        sufficient,
+    }
+}
+
+/// 容量不足（ENOSPC、クォータ超過を含む）のエラーか
+pub fn is_disk_full_error(err: &std::io::Error) -> bool {
+    if matches!(
+        err.kind(),
+        std::io::ErrorKind::StorageFull | std::io::ErrorKind::QuotaExceeded
+    ) {
+        return true;
+    }
+    // ERROR_HANDLE_DISK_FULL(39)とERROR_DISK_FULL(112)
+    cfg!(windows) && matches!(err.raw_os_error(), Some(39) | Some(112))
+}
+
+/// 2つのパスが同じデバイス上にあるか（まだ存在しないパスは親フォルダで判定）
+pub fn same_device(a: &Path, b: &Path) -> bool {
+    match (
+        device_key(&existing_ancestor(a)),
+        device_key(&existing_ancestor(b)),
+    ) {
+        (Some(a), Some(b)) => a == b,
+        _ => false,
+    }
+}
+
+/// 存在する最も近い親フォルダ（まだ作られていない出力先のため）
+fn existing_ancestor(path: &Path) -> PathBuf {
+    path.ancestors()
+        .find(|ancestor| ancestor.exists())
+        .unwrap_or(path)
+        .to_path_buf()
+}
+
+/// パスのあるデバイスの識別子
+#[cfg(unix)]
+fn device_key(path: &Path) -> Option<String> {
+    use std::os::unix::fs::MetadataExt;
+    // シンボリックリンクはリンク自体を移動するため、リンク先ではなくリンクの場所で判定する
+    std::fs::symlink_metadata(path)
+        .ok()
+        .map(|meta| meta.dev().to_string())
+}
+
+/// パスのあるデバイスの識別子（ドライブ名などのパスの先頭）
+#[cfg(not(unix))]
+fn device_key(path: &Path) -> Option<String> {
+    let path = path.canonicalize().ok()?;
+    match path.components().next()? {
+        std::path::Component::Prefix(prefix) => {
+            Some(prefix.as_os_str().to_string_lossy().to_uppercase())
+        }
+        _ => None,
+    }
+}
+
+/// バイト数を読みやすい単位に変換
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
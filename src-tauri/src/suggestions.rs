@@ -0,0 +1,255 @@
+// 未分類ファイルからの設定の提案
+//
+// 整理後に未分類フォルダ（Othersなど）に残ったファイルを拡張子ごとに集計し、
+// 組み込みのカタログ、ファイルの中身（先頭のシグネチャ）、ファイル名の傾向から
+// 「.heicを画像に追加（42件）」のような設定の変更を提案します。
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use crate::catalogue::{self, CATALOGUE};
+use crate::classify;
+use crate::{extensions, get_category, Config, IpcPath};
+
+/// 中身の判定に読み込む先頭のバイト数
+const SNIFF_LEN: usize = 16;
+
+/// ファイル名の傾向とカタログのカテゴリ
+const NAME_HINTS: &[(&str, &str)] = &[
+    ("screenshot*", "Images"),
+    ("スクリーンショット*", "Images"),
+    ("IMG_*", "Images"),
+    ("DSC*", "Images"),
+    ("PXL_*", "Images"),
+    ("VID_*", "Videos"),
+    ("*invoice*", "Documents"),
+    ("*receipt*", "Documents"),
+    ("*請求書*", "Documents"),
+    ("*領収書*", "Documents"),
+    ("*見積書*", "Documents"),
+    ("*setup*", "Installers"),
+    ("*installer*", "Installers"),
+];
+
+/// 提案の根拠
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SuggestionReason {
+    /// 組み込みのカタログに含まれる拡張子
+    Catalogue,
+    /// ファイルの中身から判定した種類
+    Content,
+    /// ファイル名の傾向
+    FileName,
+}
+
+/// 設定の変更の提案
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Suggestion {
+    /// 追加する拡張子
+    pub extension: String,
+    /// 追加先のカテゴリ
+    pub category: String,
+    /// 提案の根拠
+    pub reason: SuggestionReason,
+    /// 対象のファイル
+    pub files: Vec<IpcPath>,
+}
+
+/// 未分類フォルダにあるファイルを分析して提案を作成
+///
+/// 提案は対象のファイルが多い順に並びます
+pub fn suggest(config: &Config, output_roots: &[PathBuf]) -> Vec<Suggestion> {
+    let mut groups: HashMap<String, Vec<PathBuf>> = HashMap::new();
+    for root in output_roots {
+        for folder in config.fallback.folder_names() {
+            for file in collect_files(&classify::category_dir(root, &folder)) {
+                let name = file
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                let ext =
+                    extensions::canonical(&extensions::last(&name), &config.extension_aliases);
+                // 拡張子のないファイルは拡張子のルールで振り分けられないため対象外
+                if ext.is_empty() {
+                    continue;
+                }
+                groups.entry(ext).or_default().push(file);
+            }
+        }
+    }
+
+    let mut suggestions: Vec<Suggestion> = groups
+        .into_iter()
+        .filter(|(ext, _)| {
+            get_category(ext, &config.categories, &config.extension_aliases).is_none()
+        })
+        .filter_map(|(extension, files)| {
+            let (category, reason) = suggest_category(config, &extension, &files)?;
+            Some(Suggestion {
+                extension,
+                category,
+                reason,
+                files: files.into_iter().map(IpcPath::from).collect(),
+            })
+        })
+        .collect();
+    suggestions.sort_by(|a, b| {
+        b.files
+            .len()
+            .cmp(&a.files.len())
+            .then_with(|| a.extension.cmp(&b.extension))
+    });
+    suggestions
+}
+
+/// 提案を設定に反映（既にいずれかのカテゴリにある拡張子は追加しない）
+pub fn apply(config: &mut Config, suggestion: &Suggestion) {
+    if get_category(
+        &suggestion.extension,
+        &config.categories,
+        &config.extension_aliases,
+    )
+    .is_some()
+    {
+        return;
+    }
+    config
+        .categories
+        .entry(suggestion.category.clone())
+        .or_default()
+        .push(suggestion.extension.clone());
+}
+
+/// 拡張子の追加先のカテゴリと根拠を判断
+fn suggest_category(
+    config: &Config,
+    extension: &str,
+    files: &[PathBuf],
+) -> Option<(String, SuggestionReason)> {
+    let aliases = &config.extension_aliases;
+    if let Some(entry) = CATALOGUE.iter().find(|entry| {
+        entry
+            .extensions
+            .iter()
+            .any(|ext| extensions::canonical(ext, aliases) == extension)
+    }) {
+        return Some((
+            catalogue::target_category(config, entry, aliases),
+            SuggestionReason::Catalogue,
+        ));
+    }
+
+    let by_content = majority(
+        files.len(),
+        files.iter().filter_map(|file| {
+            let sniffed = sniff(file)?;
+            get_category(sniffed, &config.categories, aliases)
+                .or_else(|| catalogue_category(config, sniffed))
+        }),
+    );
+    if let Some(category) = by_content {
+        return Some((category, SuggestionReason::Content));
+    }
+
+    let by_name = majority(
+        files.len(),
+        files.iter().filter_map(|file| {
+            let name = file.file_name()?.to_string_lossy();
+            let (_, folder) = NAME_HINTS
+                .iter()
+                .find(|(pattern, _)| classify::matches_name(pattern, &name))?;
+            let entry = CATALOGUE.iter().find(|entry| entry.folder == *folder)?;
+            Some(catalogue::target_category(config, entry, aliases))
+        }),
+    );
+    by_name.map(|category| (category, SuggestionReason::FileName))
+}
+
+/// 過半数のファイルが同じカテゴリを示した場合にそのカテゴリを返す
+fn majority(total: usize, votes: impl Iterator<Item = String>) -> Option<String> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for category in votes {
+        *counts.entry(category).or_default() += 1;
+    }
+    counts
+        .into_iter()
+        .filter(|(_, count)| count * 2 > total)
+        .map(|(category, _)| category)
+        .next()
+}
+
+/// カタログで拡張子が属するカテゴリ（設定に合わせたカテゴリ名）
+fn catalogue_category(config: &Config, ext: &str) -> Option<String> {
+    let entry = CATALOGUE
+        .iter()
+        .find(|entry| entry.extensions.contains(&ext))?;
+    Some(catalogue::target_category(
+        config,
+        entry,
+        &config.extension_aliases,
+    ))
+}
+
+/// フォルダ内のファイルをサブフォルダも含めて列挙（読めないフォルダは無視）
+fn collect_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_dir() {
+                pending.push(entry.path());
+            } else if file_type.is_file() {
+                files.push(entry.path());
+            }
+        }
+    }
+    files
+}
+
+/// ファイルの先頭のシグネチャから種類を判定し、代表的な拡張子を返す
+fn sniff(path: &Path) -> Option<&'static str> {
+    let mut header = [0u8; SNIFF_LEN];
+    let mut file = File::open(path).ok()?;
+    let len = file.read(&mut header).ok()?;
+    let header = &header[..len];
+
+    let ext = match header {
+        [0xFF, 0xD8, 0xFF, ..] => ".jpg",
+        [0x89, b'P', b'N', b'G', ..] => ".png",
+        [b'G', b'I', b'F', b'8', ..] => ".gif",
+        [b'%', b'P', b'D', b'F', ..] => ".pdf",
+        [b'P', b'K', 0x03, 0x04, ..] => ".zip",
+        [0x1F, 0x8B, ..] => ".gz",
+        [b'7', b'z', 0xBC, 0xAF, 0x27, 0x1C, ..] => ".7z",
+        [b'R', b'a', b'r', b'!', ..] => ".rar",
+        [b'B', b'Z', b'h', ..] => ".bz2",
+        [0xFD, b'7', b'z', b'X', b'Z', 0x00, ..] => ".xz",
+        [b'I', b'D', b'3', ..] => ".mp3",
+        [b'O', b'g', b'g', b'S', ..] => ".ogg",
+        [b'f', b'L', b'a', b'C', ..] => ".flac",
+        [0x1A, 0x45, 0xDF, 0xA3, ..] => ".mkv",
+        [b'w', b'O', b'F', b'F', ..] => ".woff",
+        [b'w', b'O', b'F', b'2', ..] => ".woff2",
+        [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'E', b'B', b'P', ..] => ".webp",
+        [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'A', b'V', b'E', ..] => ".wav",
+        [b'R', b'I', b'F', b'F', _, _, _, _, b'A', b'V', b'I', b' ', ..] => ".avi",
+        [_, _, _, _, b'f', b't', b'y', b'p', brand @ ..] if brand.len() >= 4 => match &brand[..4] {
+            b"heic" | b"heix" | b"mif1" | b"msf1" => ".heic",
+            b"avif" => ".avif",
+            b"qt  " => ".mov",
+            _ => ".mp4",
+        },
+        _ => return None,
+    };
+    Some(ext)
+}
@@ -20,7 +20,13 @@ import {
 } from "@tabler/icons-react";
 
 // 型定義のインポート
-import { TabType, OrganizeProgress } from "./types";
+import {
+  TabType,
+  OrganizeProgress,
+  OrganizeSummary,
+  ScanProgress,
+  ConfigChanged,
+} from "./types";
 
 // フックのインポート
 import { useConfig } from "./hooks/useConfig";
@@ -53,6 +59,32 @@ function App() {
     deleteCategory,
     startEditCategory,
     selectInputFolder,
+    clearInputFolder,
+    addInputSource,
+    updateInputSource,
+    removeInputSource,
+    folderIssues,
+    checkFolders,
+    profiles,
+    switchProfile,
+    deleteProfile,
+    setFallback,
+    setCollisionOptions,
+    setSanitizeOptions,
+    setRollbackRetainedCopies,
+    selectCategoryOutput,
+    setCategoryOutput,
+    mergeCatalogue,
+    suggestions,
+    loadSuggestions,
+    applySuggestions,
+    sharedRuleSets,
+    exportRuleSet,
+    importRuleSet,
+    selectSharedRulesDir,
+    loadSharedRuleSets,
+    applySharedRuleSet,
+    applyConfigChanged,
   } = useConfig();
 
   const {
@@ -66,10 +98,17 @@ function App() {
     setIsProcessing,
     progress,
     setProgress,
+    summary,
+    setSummary,
     isRecursive,
     setIsRecursive,
+    isScanning,
+    scanProgress,
+    scanWarnings,
     selectFiles,
     loadFilesFromInputFolder,
+    applyScanProgress,
+    cancelScan,
     organizeFiles,
     cancelProcessing,
   } = useFiles();
@@ -92,10 +131,11 @@ function App() {
         const progress = event.payload as OrganizeProgress;
         setProgress(progress);
 
-        if (progress.current_result) {
+        // 通知は間引かれるため、前回以降に完了した結果をまとめて追加
+        if (progress.new_results && progress.new_results.length > 0) {
           setResults((prevResults) => [
             ...prevResults,
-            progress.current_result!,
+            ...progress.new_results!,
           ]);
         }
 
@@ -107,6 +147,27 @@ function App() {
       return unlistenFn;
     };
 
+    // ファイル整理完了時の集計イベントリスナー
+    const setupOrganizeSummaryListener = async () => {
+      return await listen<OrganizeSummary>("organize-summary", (event) => {
+        setSummary(event.payload);
+      });
+    };
+
+    // 入力フォルダのスキャン進捗のイベントリスナー
+    const setupScanProgressListener = async () => {
+      return await listen<ScanProgress>("scan-progress", (event) => {
+        applyScanProgress(event.payload);
+      });
+    };
+
+    // 設定ファイルの変更のイベントリスナー
+    const setupConfigChangedListener = async () => {
+      return await listen<ConfigChanged>("config-changed", (event) => {
+        applyConfigChanged(event.payload);
+      });
+    };
+
     // ドラッグ&ドロップ関連のイベントリスナー
     const setupDragDropListeners = async () => {
       const fileDropListener = await listen<{ paths: string[] }>(
@@ -133,6 +194,9 @@ function App() {
 
     // イベントリスナーのセットアップと解除
     let organizeListener: (() => void) | undefined;
+    let summaryListener: (() => void) | undefined;
+    let scanListener: (() => void) | undefined;
+    let configListener: (() => void) | undefined;
     let dragDropListeners:
       | {
           fileDropListener: () => void;
@@ -145,12 +209,27 @@ function App() {
       organizeListener = unlisten;
     });
 
+    setupOrganizeSummaryListener().then((unlisten) => {
+      summaryListener = unlisten;
+    });
+
+    setupScanProgressListener().then((unlisten) => {
+      scanListener = unlisten;
+    });
+
+    setupConfigChangedListener().then((unlisten) => {
+      configListener = unlisten;
+    });
+
     setupDragDropListeners().then((listeners) => {
       dragDropListeners = listeners;
     });
 
     return () => {
       if (organizeListener) organizeListener();
+      if (summaryListener) summaryListener();
+      if (scanListener) scanListener();
+      if (configListener) configListener();
       if (dragDropListeners) {
         dragDropListeners.fileDropListener();
         dragDropListeners.dragEnterListener();
@@ -179,12 +258,10 @@ function App() {
 
   /**
    * 入力フォルダからファイルをロード
+   * @param sources 読み込む入力元のパス（省略時は有効な入力元すべて）
    */
-  const handleLoadFilesFromInputFolder = async () => {
-    const files = await loadFilesFromInputFolder(config);
-    if (files && files.length > 0) {
-      setActiveTab("files");
-    }
+  const handleLoadFilesFromInputFolder = async (sources?: string[]) => {
+    await loadFilesFromInputFolder(config, sources);
   };
 
   /**
@@ -194,6 +271,18 @@ function App() {
     await organizeFiles(config, setActiveTab);
   };
 
+  /**
+   * 提案の反映（整理し直した結果は処理結果に追加）
+   */
+  const handleApplySuggestions = async (
+    ...args: Parameters<typeof applySuggestions>
+  ) => {
+    const reorganized = await applySuggestions(...args);
+    if (reorganized.length > 0) {
+      setResults((prevResults) => [...prevResults, ...reorganized]);
+    }
+  };
+
   /**
    * タブコンテンツのレンダリング
    */
@@ -215,9 +304,20 @@ function App() {
         <FoldersTab
           config={config}
           selectInputFolder={selectInputFolder}
+          clearInputFolder={clearInputFolder}
+          addInputSource={addInputSource}
+          updateInputSource={updateInputSource}
+          removeInputSource={removeInputSource}
+          folderIssues={folderIssues}
+          checkFolders={() => checkFolders()}
           isRecursive={isRecursive}
           setIsRecursive={setIsRecursive}
           loadFilesFromInputFolder={handleLoadFilesFromInputFolder}
+          isScanning={isScanning}
+          scanProgress={scanProgress}
+          scanWarnings={scanWarnings}
+          cancelScan={cancelScan}
+          showFiles={() => setActiveTab("files")}
         />
       ),
       settings: (
@@ -233,6 +333,22 @@ function App() {
           updateCategory={updateCategory}
           deleteCategory={deleteCategory}
           startEditCategory={startEditCategory}
+          profiles={profiles}
+          switchProfile={switchProfile}
+          deleteProfile={deleteProfile}
+          setFallback={setFallback}
+          setCollisionOptions={setCollisionOptions}
+          setSanitizeOptions={setSanitizeOptions}
+          setRollbackRetainedCopies={setRollbackRetainedCopies}
+          selectCategoryOutput={selectCategoryOutput}
+          setCategoryOutput={setCategoryOutput}
+          mergeCatalogue={mergeCatalogue}
+          sharedRuleSets={sharedRuleSets}
+          exportRuleSet={exportRuleSet}
+          importRuleSet={(mode) => importRuleSet(mode)}
+          selectSharedRulesDir={selectSharedRulesDir}
+          loadSharedRuleSets={() => loadSharedRuleSets()}
+          applySharedRuleSet={applySharedRuleSet}
         />
       ),
       results: (
@@ -241,6 +357,11 @@ function App() {
           progress={progress}
           cancelProcessing={cancelProcessing}
           results={results}
+          summary={summary}
+          outputFolder={selectedOutputFolder}
+          suggestions={suggestions}
+          loadSuggestions={loadSuggestions}
+          applySuggestions={handleApplySuggestions}
         />
       ),
       permissions: (
@@ -0,0 +1,61 @@
+import { Card, Title, Stack, Group, Button, Text } from "@mantine/core";
+import { IconFolderOpen } from "@tabler/icons-react";
+import { Config } from "../types";
+
+interface CategoryOutputCardProps {
+  config: Config | null;
+  selectCategoryOutput: (category: string) => Promise<void>;
+  setCategoryOutput: (category: string, folder: string | null) => Promise<void>;
+}
+
+/**
+ * カテゴリごとの出力先の設定
+ */
+export function CategoryOutputCard({
+  config,
+  selectCategoryOutput,
+  setCategoryOutput,
+}: CategoryOutputCardProps) {
+  const categories = Object.keys(config?.categories ?? {});
+  const outputs = config?.category_outputs ?? {};
+
+  return (
+    <Card withBorder shadow="sm" p="md">
+      <Title order={4} mb="xs">
+        カテゴリごとの出力先
+      </Title>
+      <Text size="xs" c="dimmed" mb="md">
+        出力先を設定したカテゴリは、選択した出力フォルダではなく指定したフォルダに移動します
+      </Text>
+      <Stack gap="xs">
+        {categories.map((category) => (
+          <Group key={category} justify="space-between" wrap="nowrap">
+            <Text size="sm" truncate>
+              {category}: {outputs[category] ?? "既定の出力先"}
+            </Text>
+            <Group gap="xs" wrap="nowrap">
+              <Button
+                variant="light"
+                size="xs"
+                leftSection={<IconFolderOpen size={16} />}
+                onClick={() => selectCategoryOutput(category)}
+              >
+                出力先を選択
+              </Button>
+              {outputs[category] && (
+                <Button
+                  variant="subtle"
+                  color="red"
+                  size="xs"
+                  onClick={() => setCategoryOutput(category, null)}
+                >
+                  既定に戻す
+                </Button>
+              )}
+            </Group>
+          </Group>
+        ))}
+      </Stack>
+    </Card>
+  );
+}
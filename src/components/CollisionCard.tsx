@@ -0,0 +1,58 @@
+import { Card, Title, Stack, Checkbox, Text } from "@mantine/core";
+import { CollisionOptions, Config } from "../types";
+
+/** 設定に衝突の判定方法がない場合の既定値 */
+const DEFAULT_COLLISIONS: CollisionOptions = {
+  case_insensitive: false,
+  normalize_names: false,
+};
+
+interface CollisionCardProps {
+  config: Config | null;
+  setCollisionOptions: (collisions: CollisionOptions) => Promise<void>;
+}
+
+/**
+ * 移動先のファイル名の衝突の判定方法の設定
+ */
+export function CollisionCard({
+  config,
+  setCollisionOptions,
+}: CollisionCardProps) {
+  const collisions = config?.collisions ?? DEFAULT_COLLISIONS;
+
+  return (
+    <Card withBorder shadow="sm" p="md">
+      <Title order={4} mb="xs">
+        同名ファイルの判定
+      </Title>
+      <Text size="xs" c="dimmed" mb="md">
+        見た目が同じ名前（macOSのNFDとNFCなど）は同名として扱い、連番を付けます
+      </Text>
+      <Stack>
+        <Checkbox
+          checked={collisions.case_insensitive}
+          disabled={!config}
+          onChange={(e) =>
+            setCollisionOptions({
+              ...collisions,
+              case_insensitive: e.currentTarget.checked,
+            })
+          }
+          label="大文字と小文字を区別しない（exFAT、SMBなどの移動先向け）"
+        />
+        <Checkbox
+          checked={collisions.normalize_names}
+          disabled={!config}
+          onChange={(e) =>
+            setCollisionOptions({
+              ...collisions,
+              normalize_names: e.currentTarget.checked,
+            })
+          }
+          label="移動時にファイル名をNFCに正規化する"
+        />
+      </Stack>
+    </Card>
+  );
+}
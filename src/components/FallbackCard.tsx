@@ -0,0 +1,73 @@
+import { useEffect, useState } from "react";
+import { Card, Title, Stack, Select, TextInput, Button } from "@mantine/core";
+import { Config, FallbackMode, FallbackOptions } from "../types";
+
+/** 未分類ファイルの扱いの選択肢 */
+const FALLBACK_MODES: { value: FallbackMode; label: string }[] = [
+  { value: "folder", label: "未分類フォルダに移動" },
+  { value: "by_extension", label: "未分類フォルダ内で拡張子ごとに分ける" },
+  { value: "leave_in_place", label: "移動しない" },
+  { value: "review", label: "確認待ちフォルダに移動" },
+];
+
+/** 設定に未分類ファイルの扱いがない場合の既定値 */
+const DEFAULT_FALLBACK: FallbackOptions = {
+  mode: "folder",
+  folder_name: "Others",
+  review_folder_name: "_Review",
+};
+
+interface FallbackCardProps {
+  config: Config | null;
+  setFallback: (fallback: FallbackOptions) => Promise<void>;
+}
+
+/**
+ * どのカテゴリにも当てはまらないファイルの扱いの設定
+ */
+export function FallbackCard({ config, setFallback }: FallbackCardProps) {
+  const [draft, setDraft] = useState<FallbackOptions>(DEFAULT_FALLBACK);
+
+  useEffect(() => {
+    setDraft(config?.fallback ?? DEFAULT_FALLBACK);
+  }, [config?.fallback]);
+
+  return (
+    <Card withBorder shadow="sm" p="md">
+      <Title order={4} mb="md">
+        未分類ファイルの扱い
+      </Title>
+      <Stack>
+        <Select
+          data={FALLBACK_MODES}
+          value={draft.mode}
+          onChange={(value) =>
+            value && setDraft({ ...draft, mode: value as FallbackMode })
+          }
+        />
+        {(draft.mode === "folder" || draft.mode === "by_extension") && (
+          <TextInput
+            label="未分類フォルダ名"
+            value={draft.folder_name}
+            onChange={(e) => setDraft({ ...draft, folder_name: e.target.value })}
+          />
+        )}
+        {draft.mode === "review" && (
+          <TextInput
+            label="確認待ちフォルダ名"
+            value={draft.review_folder_name}
+            onChange={(e) =>
+              setDraft({ ...draft, review_folder_name: e.target.value })
+            }
+          />
+        )}
+        <Button
+          disabled={!draft.folder_name || !draft.review_folder_name}
+          onClick={() => setFallback(draft)}
+        >
+          保存
+        </Button>
+      </Stack>
+    </Card>
+  );
+}
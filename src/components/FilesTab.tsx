@@ -16,6 +16,8 @@ import {
 } from "@tabler/icons-react";
 import { RefObject } from "react";
 import { FileOrganizer } from "./FileOrganizer";
+import { FilePath } from "../types";
+import { fileName, isUndecodablePath } from "../utils/path";
 
 interface FilesTabProps {
   dropZoneRef: RefObject<HTMLDivElement>;
@@ -23,7 +25,7 @@ interface FilesTabProps {
   selectFiles: () => Promise<void>;
   selectOutputFolder: () => Promise<void>;
   selectedOutputFolder: string;
-  selectedFiles: string[];
+  selectedFiles: FilePath[];
   organizeFiles: () => Promise<void>;
   isProcessing: boolean;
 }
@@ -95,7 +97,8 @@ export function FilesTab({
                   >
                     {selectedFiles.map((file, index) => (
                       <Box key={index} py="xs">
-                        {file.split("/").pop()}
+                        {fileName(file)}
+                        {isUndecodablePath(file) && " (文字コード不明)"}
                       </Box>
                     ))}
                   </Paper>
@@ -1,3 +1,4 @@
+import { useState } from "react";
 import {
   Container,
   Title,
@@ -7,25 +8,78 @@ import {
   Box,
   Text,
   Checkbox,
+  Group,
 } from "@mantine/core";
-import { IconFolderOpen } from "@tabler/icons-react";
-import { Config } from "../types";
+import { IconFolderOpen, IconFolderPlus } from "@tabler/icons-react";
+import {
+  Config,
+  FolderIssue,
+  InputSource,
+  ScanProgress,
+  ScanWarning,
+} from "../types";
+import { displayPath } from "../utils/path";
 
 interface FoldersTabProps {
   config: Config | null;
   selectInputFolder: () => Promise<void>;
+  clearInputFolder: () => Promise<void>;
+  addInputSource: () => Promise<void>;
+  updateInputSource: (source: InputSource) => Promise<void>;
+  removeInputSource: (path: string) => Promise<void>;
+  folderIssues: FolderIssue[];
+  checkFolders: () => Promise<void>;
   isRecursive: boolean;
   setIsRecursive: (value: boolean) => void;
-  loadFilesFromInputFolder: () => Promise<void>;
+  loadFilesFromInputFolder: (sources?: string[]) => Promise<void>;
+  isScanning: boolean;
+  scanProgress: ScanProgress | null;
+  scanWarnings: ScanWarning[];
+  cancelScan: () => Promise<void>;
+  showFiles: () => void;
 }
 
 export function FoldersTab({
   config,
   selectInputFolder,
+  clearInputFolder,
+  addInputSource,
+  updateInputSource,
+  removeInputSource,
+  folderIssues,
+  checkFolders,
   isRecursive,
   setIsRecursive,
   loadFilesFromInputFolder,
+  isScanning,
+  scanProgress,
+  scanWarnings,
+  cancelScan,
+  showFiles,
 }: FoldersTabProps) {
+  // 今回読み込む入力元（未選択の場合は有効な入力元すべて）
+  const [selectedSources, setSelectedSources] = useState<string[] | null>(
+    null,
+  );
+
+  const defaultSources = [
+    ...(config?.input_folder ? [config.input_folder] : []),
+    ...(config?.input_sources ?? [])
+      .filter((source) => source.enabled && source.path !== config?.input_folder)
+      .map((source) => source.path),
+  ];
+  const sourcesToScan = selectedSources ?? defaultSources;
+
+  /**
+   * 今回読み込む入力元を切り替える
+   * @param path 入力元のパス
+   * @param checked 読み込む場合はtrue
+   */
+  const toggleSelectedSource = (path: string, checked: boolean) => {
+    const next = sourcesToScan.filter((selected) => selected !== path);
+    setSelectedSources(checked ? [...next, path] : next);
+  };
+
   return (
     <Container>
       <Title order={2} mb="md">
@@ -33,27 +87,159 @@ export function FoldersTab({
       </Title>
       <Paper p="lg" withBorder>
         <Stack>
-          <Button
-            leftSection={<IconFolderOpen size={20} />}
-            onClick={selectInputFolder}
-          >
-            仕分け元フォルダを設定
-          </Button>
+          <Group>
+            <Button
+              leftSection={<IconFolderOpen size={20} />}
+              onClick={selectInputFolder}
+            >
+              仕分け元フォルダを設定
+            </Button>
+            <Button
+              variant="light"
+              leftSection={<IconFolderPlus size={20} />}
+              onClick={addInputSource}
+            >
+              入力元を追加
+            </Button>
+          </Group>
 
-          {config?.input_folder && (
+          {folderIssues.length > 0 && (
             <Box>
-              <Text fw={500} mb="md">
-                仕分け元フォルダ: {config.input_folder}
+              <Text size="sm" c="orange">
+                保存済みフォルダの問題: {folderIssues.length}件
               </Text>
+              {folderIssues.map((issue, index) => (
+                <Text key={index} size="xs" c="dimmed">
+                  {issue.path}: {issue.message}
+                </Text>
+              ))}
+            </Box>
+          )}
+          <Button variant="subtle" onClick={checkFolders}>
+            保存済みフォルダを確認
+          </Button>
+
+          {(config?.input_folder || !!config?.input_sources?.length) && (
+            <Box>
+              {config?.input_folder && (
+                <Box mb="md">
+                  <Checkbox
+                    label={`仕分け元フォルダ: ${config.input_folder}`}
+                    checked={sourcesToScan.includes(config.input_folder)}
+                    onChange={(e) =>
+                      toggleSelectedSource(
+                        config.input_folder as string,
+                        e.target.checked,
+                      )
+                    }
+                  />
+                  <Button variant="subtle" color="red" size="xs" onClick={clearInputFolder}>
+                    設定を解除
+                  </Button>
+                </Box>
+              )}
+              {config?.input_sources
+                ?.filter((source) => source.path !== config?.input_folder)
+                .map((source) => (
+                  <Box key={source.path} mb="md">
+                    <Checkbox
+                      label={`${source.path}${source.output_folder ? ` → ${source.output_folder}` : ""}`}
+                      checked={sourcesToScan.includes(source.path)}
+                      onChange={(e) =>
+                        toggleSelectedSource(source.path, e.target.checked)
+                      }
+                    />
+                    <Group gap="md" mt={4} ml="xl">
+                      <Checkbox
+                        size="xs"
+                        label="有効"
+                        checked={source.enabled}
+                        onChange={(e) =>
+                          updateInputSource({
+                            ...source,
+                            enabled: e.target.checked,
+                          })
+                        }
+                      />
+                      <Checkbox
+                        size="xs"
+                        label="サブフォルダも含める"
+                        checked={source.recursive}
+                        onChange={(e) =>
+                          updateInputSource({
+                            ...source,
+                            recursive: e.target.checked,
+                          })
+                        }
+                      />
+                      <Button
+                        variant="subtle"
+                        color="red"
+                        size="xs"
+                        onClick={() => {
+                          setSelectedSources(
+                            (prev) =>
+                              prev?.filter((path) => path !== source.path) ??
+                              null,
+                          );
+                          removeInputSource(source.path);
+                        }}
+                      >
+                        削除
+                      </Button>
+                    </Group>
+                  </Box>
+                ))}
               <Stack>
                 <Checkbox
                   label="サブフォルダも含めて検索する"
                   checked={isRecursive}
                   onChange={(e) => setIsRecursive(e.target.checked)}
                 />
-                <Button onClick={loadFilesFromInputFolder}>
-                  フォルダからファイルを読み込む
-                </Button>
+                {isScanning ? (
+                  <Button variant="outline" color="red" onClick={cancelScan}>
+                    読み込みを中止
+                  </Button>
+                ) : (
+                  <Button
+                    disabled={sourcesToScan.length === 0}
+                    onClick={() =>
+                      loadFilesFromInputFolder(selectedSources ?? undefined)
+                    }
+                  >
+                    フォルダからファイルを読み込む
+                  </Button>
+                )}
+                {scanProgress && (
+                  <Text size="sm" c="black">
+                    {isScanning ? "読み込み中... " : ""}
+                    {scanProgress.files_found}ファイル /{" "}
+                    {scanProgress.dirs_visited}フォルダ
+                    {scanProgress.cancelled && "（中断）"}
+                  </Text>
+                )}
+                {isScanning && scanProgress?.current_dir && (
+                  <Text size="xs" c="dimmed" truncate>
+                    {displayPath(scanProgress.current_dir)}
+                  </Text>
+                )}
+                {scanWarnings.length > 0 && (
+                  <Box>
+                    <Text size="sm" c="orange">
+                      読み取れなかった項目: {scanWarnings.length}件
+                    </Text>
+                    {scanWarnings.slice(0, 5).map((warning, index) => (
+                      <Text key={index} size="xs" c="dimmed" truncate>
+                        {displayPath(warning.path)}: {warning.message}
+                      </Text>
+                    ))}
+                  </Box>
+                )}
+                {!isScanning && (scanProgress?.files_found ?? 0) > 0 && (
+                  <Button variant="light" onClick={showFiles}>
+                    読み込んだファイルを表示
+                  </Button>
+                )}
               </Stack>
             </Box>
           )}
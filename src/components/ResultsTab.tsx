@@ -12,13 +12,39 @@ import {
   Badge,
   Flex,
 } from "@mantine/core";
-import { FileResult, OrganizeProgress } from "../types";
+import {
+  FileResult,
+  OrganizeProgress,
+  OrganizeSummary,
+  Suggestion,
+} from "../types";
+import { displayPath, fileName } from "../utils/path";
+import { formatBytes } from "../utils/format";
+import { SuggestionsCard } from "./SuggestionsCard";
+
+/**
+ * 秒数を「分:秒」形式に変換
+ */
+function formatDuration(seconds: number) {
+  const minutes = Math.floor(seconds / 60);
+  const rest = seconds % 60;
+  return `${minutes}:${rest.toString().padStart(2, "0")}`;
+}
 
 interface ResultsTabProps {
   isProcessing: boolean;
   progress: OrganizeProgress | null;
   cancelProcessing: () => Promise<void>;
   results: FileResult[];
+  summary: OrganizeSummary | null;
+  outputFolder: string | null;
+  suggestions: Suggestion[];
+  loadSuggestions: (outputFolder: string) => Promise<void>;
+  applySuggestions: (
+    suggestions: Suggestion[],
+    outputFolder: string,
+    reorganize: boolean,
+  ) => Promise<void>;
 }
 
 export function ResultsTab({
@@ -26,6 +52,11 @@ export function ResultsTab({
   progress,
   cancelProcessing,
   results,
+  summary,
+  outputFolder,
+  suggestions,
+  loadSuggestions,
+  applySuggestions,
 }: ResultsTabProps) {
   return (
     <Container>
@@ -51,7 +82,9 @@ export function ResultsTab({
             </Group>
             <Progress
               value={
-                progress
+                progress && progress.total_bytes > 0
+                  ? (progress.processed_bytes / progress.total_bytes) * 100
+                  : progress && progress.total_files > 0
                   ? (progress.processed_files / progress.total_files) * 100
                   : 0
               }
@@ -59,6 +92,65 @@ export function ResultsTab({
               striped
               animated={isProcessing}
             />
+            {progress && progress.total_bytes > 0 && (
+              <Group mt="xs" justify="space-between">
+                <Text size="sm" c="black">
+                  {formatBytes(progress.processed_bytes)} /{" "}
+                  {formatBytes(progress.total_bytes)}
+                  {progress.bytes_per_second > 0 &&
+                    ` (${formatBytes(progress.bytes_per_second)}/s)`}
+                </Text>
+                {progress.eta_seconds != null && (
+                  <Text size="sm" c="black">
+                    残り約 {formatDuration(progress.eta_seconds)}
+                  </Text>
+                )}
+              </Group>
+            )}
+            {progress?.current_file && (
+              <Text
+                size="xs"
+                c="black"
+                truncate
+                title={displayPath(progress.current_file)}
+              >
+                {fileName(progress.current_file)}
+                {progress.current_file_total_bytes > 0 &&
+                  progress.current_file_bytes > 0 &&
+                  ` — ${formatBytes(progress.current_file_bytes)} / ${formatBytes(
+                    progress.current_file_total_bytes
+                  )}`}
+              </Text>
+            )}
+          </Box>
+        )}
+
+        {summary && (
+          <Box mb="lg">
+            <Group mb="xs">
+              <Badge color="green">成功 {summary.succeeded}</Badge>
+              <Badge color="red">失敗 {summary.failed}</Badge>
+              <Badge color="gray">スキップ {summary.skipped}</Badge>
+              {summary.source_retained > 0 && (
+                <Badge color="yellow">
+                  元ファイルが残存 {summary.source_retained}
+                </Badge>
+              )}
+              {summary.cancelled && <Badge color="orange">中断</Badge>}
+              {summary.disk_full && (
+                <Badge color="red">空き容量不足で停止</Badge>
+              )}
+              <Text size="sm" c="black">
+                {(summary.elapsed_ms / 1000).toFixed(1)} 秒
+              </Text>
+            </Group>
+            <Group gap="xs">
+              {Object.entries(summary.per_category).map(([category, count]) => (
+                <Badge key={category} variant="outline">
+                  {category}: {count}
+                </Badge>
+              ))}
+            </Group>
           </Box>
         )}
 
@@ -69,14 +161,23 @@ export function ResultsTab({
                 <Flex justify="space-between" align="center">
                   <Box style={{ flex: 1, overflow: "hidden" }}>
                     <Text truncate c="black">
-                      {result.file_path.split("/").pop()}
+                      {fileName(result.file_path)}
                     </Text>
-                    <Text size="xs" c="black" truncate title={result.file_path}>
-                      {result.file_path}
+                    <Text
+                      size="xs"
+                      c="black"
+                      truncate
+                      title={displayPath(result.file_path)}
+                    >
+                      {displayPath(result.file_path)}
                     </Text>
                   </Box>
                   <Box ml="md">
-                    {result.success ? (
+                    {result.outcome === "skipped" ? (
+                      <Badge color="gray">{result.message}</Badge>
+                    ) : result.outcome === "source_retained" ? (
+                      <Badge color="yellow">{result.message}</Badge>
+                    ) : result.success ? (
                       <Badge color="green">{result.message}</Badge>
                     ) : (
                       <Badge color="red">{result.message}</Badge>
@@ -92,6 +193,15 @@ export function ResultsTab({
           </Text>
         )}
       </Paper>
+
+      {!isProcessing && (
+        <SuggestionsCard
+          outputFolder={outputFolder}
+          suggestions={suggestions}
+          loadSuggestions={loadSuggestions}
+          applySuggestions={applySuggestions}
+        />
+      )}
     </Container>
   );
 }
@@ -0,0 +1,32 @@
+import { Card, Title, Checkbox, Text } from "@mantine/core";
+import { Config } from "../types";
+
+interface RetainedCopyCardProps {
+  config: Config | null;
+  setRollbackRetainedCopies: (enabled: boolean) => Promise<void>;
+}
+
+/**
+ * コピー後に元ファイルを削除できなかった場合の扱いの設定
+ */
+export function RetainedCopyCard({
+  config,
+  setRollbackRetainedCopies,
+}: RetainedCopyCardProps) {
+  return (
+    <Card withBorder shadow="sm" p="md">
+      <Title order={4} mb="xs">
+        元ファイルを削除できない場合
+      </Title>
+      <Text size="xs" c="dimmed" mb="md">
+        別のドライブへの移動でコピー後に元ファイルを削除できないと、両方に同じファイルが残ります
+      </Text>
+      <Checkbox
+        checked={config?.rollback_retained_copies ?? false}
+        disabled={!config}
+        onChange={(e) => setRollbackRetainedCopies(e.currentTarget.checked)}
+        label="移動先のコピーを削除して元の状態に戻す"
+      />
+    </Card>
+  );
+}
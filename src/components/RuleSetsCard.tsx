@@ -0,0 +1,114 @@
+import { Card, Title, Stack, Group, Button, Text, Box } from "@mantine/core";
+import { IconDownload, IconUpload, IconFolderOpen } from "@tabler/icons-react";
+import { Config, ImportMode, SharedRuleSet } from "../types";
+
+interface RuleSetsCardProps {
+  config: Config | null;
+  sharedRuleSets: SharedRuleSet[];
+  exportRuleSet: () => Promise<void>;
+  importRuleSet: (mode: ImportMode) => Promise<void>;
+  selectSharedRulesDir: () => Promise<void>;
+  loadSharedRuleSets: () => Promise<void>;
+  applySharedRuleSet: (ruleSet: SharedRuleSet, mode: ImportMode) => Promise<void>;
+}
+
+/**
+ * ルールセットの書き出し・読み込みと共有フォルダ
+ */
+export function RuleSetsCard({
+  config,
+  sharedRuleSets,
+  exportRuleSet,
+  importRuleSet,
+  selectSharedRulesDir,
+  loadSharedRuleSets,
+  applySharedRuleSet,
+}: RuleSetsCardProps) {
+  return (
+    <Card withBorder shadow="sm" p="md">
+      <Title order={4} mb="md">
+        ルールセット
+      </Title>
+      <Stack>
+        <Group>
+          <Button
+            size="xs"
+            leftSection={<IconDownload size={16} />}
+            onClick={exportRuleSet}
+          >
+            書き出す
+          </Button>
+          <Button
+            size="xs"
+            variant="outline"
+            leftSection={<IconUpload size={16} />}
+            onClick={() => importRuleSet("merge")}
+          >
+            読み込んで追加
+          </Button>
+          <Button
+            size="xs"
+            variant="outline"
+            color="red"
+            leftSection={<IconUpload size={16} />}
+            onClick={() => importRuleSet("replace")}
+          >
+            読み込んで置き換え
+          </Button>
+        </Group>
+
+        <Group>
+          <Button
+            size="xs"
+            variant="subtle"
+            leftSection={<IconFolderOpen size={16} />}
+            onClick={selectSharedRulesDir}
+          >
+            共有フォルダを設定
+          </Button>
+          {config?.shared_rules_dir && (
+            <Button size="xs" variant="subtle" onClick={loadSharedRuleSets}>
+              共有フォルダを読み込む
+            </Button>
+          )}
+        </Group>
+        {config?.shared_rules_dir && (
+          <Text size="xs" c="dimmed">
+            {config.shared_rules_dir}
+          </Text>
+        )}
+
+        {sharedRuleSets.map((ruleSet) => (
+          <Box key={ruleSet.name}>
+            <Group justify="space-between">
+              <Text size="sm">{ruleSet.name}</Text>
+              {ruleSet.rule_set ? (
+                <Group gap="xs">
+                  <Button
+                    size="xs"
+                    variant="light"
+                    onClick={() => applySharedRuleSet(ruleSet, "merge")}
+                  >
+                    追加
+                  </Button>
+                  <Button
+                    size="xs"
+                    variant="light"
+                    color="red"
+                    onClick={() => applySharedRuleSet(ruleSet, "replace")}
+                  >
+                    置き換え
+                  </Button>
+                </Group>
+              ) : (
+                <Text size="xs" c="red">
+                  {ruleSet.error}
+                </Text>
+              )}
+            </Group>
+          </Box>
+        ))}
+      </Stack>
+    </Card>
+  );
+}
@@ -0,0 +1,72 @@
+import { useEffect, useState } from "react";
+import {
+  Card,
+  Title,
+  Stack,
+  Select,
+  TextInput,
+  Button,
+  Text,
+} from "@mantine/core";
+import { Config, SanitizeMode, SanitizeOptions } from "../types";
+
+/** 使えない名前の扱いの選択肢 */
+const SANITIZE_MODES: { value: SanitizeMode; label: string }[] = [
+  { value: "replace", label: "使える名前に置き換えて移動" },
+  { value: "reject", label: "移動せずにエラーにする" },
+];
+
+/** 設定に使えない名前の扱いがない場合の既定値 */
+const DEFAULT_SANITIZE: SanitizeOptions = {
+  mode: "replace",
+  replacement: "_",
+};
+
+interface SanitizeCardProps {
+  config: Config | null;
+  setSanitizeOptions: (sanitize: SanitizeOptions) => Promise<void>;
+}
+
+/**
+ * 移動先のファイルシステムで使えない名前の扱いの設定
+ */
+export function SanitizeCard({ config, setSanitizeOptions }: SanitizeCardProps) {
+  const [draft, setDraft] = useState<SanitizeOptions>(DEFAULT_SANITIZE);
+
+  useEffect(() => {
+    setDraft(config?.sanitize ?? DEFAULT_SANITIZE);
+  }, [config?.sanitize]);
+
+  return (
+    <Card withBorder shadow="sm" p="md">
+      <Title order={4} mb="xs">
+        移動先で使えないファイル名
+      </Title>
+      <Text size="xs" c="dimmed" mb="md">
+        FAT32・exFAT・SMBなどでは「:」「?」、末尾のピリオド、CONやNULなどの名前は使えません
+      </Text>
+      <Stack>
+        <Select
+          data={SANITIZE_MODES}
+          value={draft.mode}
+          onChange={(value) =>
+            value && setDraft({ ...draft, mode: value as SanitizeMode })
+          }
+        />
+        {draft.mode === "replace" && (
+          <TextInput
+            label="使えない文字の置き換え先"
+            value={draft.replacement}
+            onChange={(e) => setDraft({ ...draft, replacement: e.target.value })}
+          />
+        )}
+        <Button
+          disabled={!config || !draft.replacement}
+          onClick={() => setSanitizeOptions(draft)}
+        >
+          保存
+        </Button>
+      </Stack>
+    </Card>
+  );
+}
@@ -1,3 +1,4 @@
+import { useState } from "react";
 import {
   Container,
   Title,
@@ -18,8 +19,42 @@ import {
   IconEdit,
   IconTrash,
   IconPlus,
+  IconDownload,
 } from "@tabler/icons-react";
-import { Config } from "../types";
+import {
+  CollisionOptions,
+  Config,
+  FallbackOptions,
+  ImportMode,
+  ProfileList,
+  RenameRule,
+  SanitizeOptions,
+  SharedRuleSet,
+} from "../types";
+import { RuleSetsCard } from "./RuleSetsCard";
+import { CategoryOutputCard } from "./CategoryOutputCard";
+import { FallbackCard } from "./FallbackCard";
+import { CollisionCard } from "./CollisionCard";
+import { RetainedCopyCard } from "./RetainedCopyCard";
+import { SanitizeCard } from "./SanitizeCard";
+
+/**
+ * ファイル名の変更ルールの表示名
+ */
+function renameRuleLabel(rule: RenameRule) {
+  switch (rule.type) {
+    case "lowercase_extension":
+      return "拡張子を小文字に";
+    case "replace_spaces":
+      return `空白を「${rule.with ?? "_"}」に置換`;
+    case "strip_duplicate_suffix":
+      return "(1) などを削除";
+    case "normalize_unicode":
+      return "Unicode正規化 (NFC)";
+    case "date_prefix":
+      return `日付を先頭に付与 (${rule.format ?? "%Y-%m-%d_"})`;
+  }
+}
 
 interface SettingsTabProps {
   config: Config | null;
@@ -33,6 +68,22 @@ interface SettingsTabProps {
   updateCategory: () => void;
   deleteCategory: (category: string) => void;
   startEditCategory: (category: string) => void;
+  profiles: ProfileList | null;
+  switchProfile: (name: string | null) => Promise<void>;
+  deleteProfile: (name: string) => Promise<void>;
+  setFallback: (fallback: FallbackOptions) => Promise<void>;
+  setCollisionOptions: (collisions: CollisionOptions) => Promise<void>;
+  setSanitizeOptions: (sanitize: SanitizeOptions) => Promise<void>;
+  setRollbackRetainedCopies: (enabled: boolean) => Promise<void>;
+  selectCategoryOutput: (category: string) => Promise<void>;
+  setCategoryOutput: (category: string, folder: string | null) => Promise<void>;
+  mergeCatalogue: () => Promise<void>;
+  sharedRuleSets: SharedRuleSet[];
+  exportRuleSet: () => Promise<void>;
+  importRuleSet: (mode: ImportMode) => Promise<void>;
+  selectSharedRulesDir: () => Promise<void>;
+  loadSharedRuleSets: () => Promise<void>;
+  applySharedRuleSet: (ruleSet: SharedRuleSet, mode: ImportMode) => Promise<void>;
 }
 
 export function SettingsTab({
@@ -47,12 +98,108 @@ export function SettingsTab({
   updateCategory,
   deleteCategory,
   startEditCategory,
+  profiles,
+  switchProfile,
+  deleteProfile,
+  setFallback,
+  setCollisionOptions,
+  setSanitizeOptions,
+  setRollbackRetainedCopies,
+  selectCategoryOutput,
+  setCategoryOutput,
+  mergeCatalogue,
+  sharedRuleSets,
+  exportRuleSet,
+  importRuleSet,
+  selectSharedRulesDir,
+  loadSharedRuleSets,
+  applySharedRuleSet,
 }: SettingsTabProps) {
+  const [newProfile, setNewProfile] = useState("");
+
+  /**
+   * 画面の言語に合わせたカテゴリの表示名（未設定の場合はフォルダ名）
+   */
+  const displayName = (category: string) => {
+    const names = config?.display_names?.[category];
+    if (!names) return category;
+    return navigator.language.startsWith("ja") ? names.ja : names.en;
+  };
+
   return (
     <Container>
-      <Title order={2} mb="md">
-        カテゴリ設定
-      </Title>
+      {profiles && (
+        <Card withBorder shadow="sm" p="md" mb="md">
+          <Title order={4} mb="xs">
+            プロファイル
+          </Title>
+          <Text size="xs" c="dimmed" mb="md">
+            {profiles.config_path}
+          </Text>
+          {!profiles.path_overridden && (
+            <Stack>
+              <Flex gap="xs" wrap="wrap">
+                <Button
+                  size="xs"
+                  variant={profiles.active ? "outline" : "filled"}
+                  onClick={() => switchProfile(null)}
+                >
+                  既定
+                </Button>
+                {profiles.profiles.map((name) => (
+                  <Group key={name} gap={2}>
+                    <Button
+                      size="xs"
+                      variant={profiles.active === name ? "filled" : "outline"}
+                      onClick={() => switchProfile(name)}
+                    >
+                      {name}
+                    </Button>
+                    {profiles.active !== name && (
+                      <ActionIcon
+                        color="red"
+                        variant="subtle"
+                        onClick={() => deleteProfile(name)}
+                      >
+                        <IconTrash size={16} />
+                      </ActionIcon>
+                    )}
+                  </Group>
+                ))}
+              </Flex>
+              <Group>
+                <TextInput
+                  value={newProfile}
+                  onChange={(e) => setNewProfile(e.target.value)}
+                  placeholder="新しいプロファイル名"
+                />
+                <Button
+                  leftSection={<IconPlus size={18} />}
+                  disabled={!newProfile}
+                  onClick={async () => {
+                    await switchProfile(newProfile);
+                    setNewProfile("");
+                  }}
+                >
+                  作成して切り替え
+                </Button>
+              </Group>
+            </Stack>
+          )}
+        </Card>
+      )}
+
+      <Group justify="space-between" mb="md">
+        <Title order={2}>カテゴリ設定</Title>
+        <Button
+          variant="light"
+          leftSection={<IconDownload size={18} />}
+          onClick={mergeCatalogue}
+          disabled={!config}
+        >
+          組み込みのカタログから追加
+        </Button>
+      </Group>
       <Stack>
         {config &&
           Object.entries(config.categories).map(([category, extensions]) => (
@@ -87,7 +234,14 @@ export function SettingsTab({
               ) : (
                 <Flex justify="space-between" align="flex-start">
                   <Box>
-                    <Text fw={700}>{category}</Text>
+                    <Group gap="xs">
+                      <Text fw={700}>{displayName(category)}</Text>
+                      {displayName(category) !== category && (
+                        <Text size="xs" c="dimmed">
+                          {category}
+                        </Text>
+                      )}
+                    </Group>
                     <Flex gap="xs" wrap="wrap" mt="xs">
                       {extensions.map((ext, i) => (
                         <Badge key={i} color="blue" variant="light">
@@ -95,6 +249,24 @@ export function SettingsTab({
                         </Badge>
                       ))}
                     </Flex>
+                    {config.subcategories?.[category]?.length ? (
+                      <Flex gap="xs" wrap="wrap" mt="xs">
+                        {config.subcategories[category].map((subcategory) => (
+                          <Badge key={subcategory.name} color="gray" variant="outline">
+                            {category}/{subcategory.name}
+                          </Badge>
+                        ))}
+                      </Flex>
+                    ) : null}
+                    {config.rename_rules?.[category]?.length ? (
+                      <Flex gap="xs" wrap="wrap" mt="xs">
+                        {config.rename_rules[category].map((rule, i) => (
+                          <Badge key={i} color="teal" variant="outline">
+                            {i + 1}. {renameRuleLabel(rule)}
+                          </Badge>
+                        ))}
+                      </Flex>
+                    ) : null}
                   </Box>
                   <Group>
                     <ActionIcon
@@ -143,6 +315,36 @@ export function SettingsTab({
             </Button>
           </Stack>
         </Card>
+
+        <CategoryOutputCard
+          config={config}
+          selectCategoryOutput={selectCategoryOutput}
+          setCategoryOutput={setCategoryOutput}
+        />
+
+        <FallbackCard config={config} setFallback={setFallback} />
+
+        <CollisionCard
+          config={config}
+          setCollisionOptions={setCollisionOptions}
+        />
+
+        <SanitizeCard config={config} setSanitizeOptions={setSanitizeOptions} />
+
+        <RetainedCopyCard
+          config={config}
+          setRollbackRetainedCopies={setRollbackRetainedCopies}
+        />
+
+        <RuleSetsCard
+          config={config}
+          sharedRuleSets={sharedRuleSets}
+          exportRuleSet={exportRuleSet}
+          importRuleSet={importRuleSet}
+          selectSharedRulesDir={selectSharedRulesDir}
+          loadSharedRuleSets={loadSharedRuleSets}
+          applySharedRuleSet={applySharedRuleSet}
+        />
       </Stack>
     </Container>
   );
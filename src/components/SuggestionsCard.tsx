@@ -0,0 +1,107 @@
+import { useEffect, useState } from "react";
+import {
+  Card,
+  Title,
+  Stack,
+  Group,
+  Text,
+  Button,
+  Checkbox,
+  Badge,
+} from "@mantine/core";
+import { Suggestion, SuggestionReason } from "../types";
+
+/** 提案の根拠の表示名 */
+const REASON_LABELS: Record<SuggestionReason, string> = {
+  catalogue: "カタログ",
+  content: "ファイルの中身",
+  file_name: "ファイル名",
+};
+
+interface SuggestionsCardProps {
+  outputFolder: string | null;
+  suggestions: Suggestion[];
+  loadSuggestions: (outputFolder: string) => Promise<void>;
+  applySuggestions: (
+    suggestions: Suggestion[],
+    outputFolder: string,
+    reorganize: boolean,
+  ) => Promise<void>;
+}
+
+/**
+ * 未分類フォルダに残ったファイルからの設定の提案
+ */
+export function SuggestionsCard({
+  outputFolder,
+  suggestions,
+  loadSuggestions,
+  applySuggestions,
+}: SuggestionsCardProps) {
+  const [selected, setSelected] = useState<string[]>([]);
+  const [reorganize, setReorganize] = useState(true);
+
+  useEffect(() => {
+    setSelected(suggestions.map((suggestion) => suggestion.extension));
+  }, [suggestions]);
+
+  if (!outputFolder) return null;
+
+  return (
+    <Card withBorder shadow="sm" p="md" mt="md">
+      <Group justify="space-between" mb="md">
+        <Title order={4}>未分類ファイルからの提案</Title>
+        <Button
+          size="xs"
+          variant="outline"
+          onClick={() => loadSuggestions(outputFolder)}
+        >
+          分析
+        </Button>
+      </Group>
+      {suggestions.length > 0 ? (
+        <Stack>
+          {suggestions.map((suggestion) => (
+            <Group key={suggestion.extension} justify="space-between">
+              <Checkbox
+                checked={selected.includes(suggestion.extension)}
+                onChange={(e) =>
+                  setSelected(
+                    e.currentTarget.checked
+                      ? [...selected, suggestion.extension]
+                      : selected.filter((ext) => ext !== suggestion.extension),
+                  )
+                }
+                label={`${suggestion.extension} を ${suggestion.category} に追加（${suggestion.files.length}件）`}
+              />
+              <Badge variant="outline">{REASON_LABELS[suggestion.reason]}</Badge>
+            </Group>
+          ))}
+          <Checkbox
+            checked={reorganize}
+            onChange={(e) => setReorganize(e.currentTarget.checked)}
+            label="反映後に対象のファイルを整理し直す"
+          />
+          <Button
+            disabled={selected.length === 0}
+            onClick={() =>
+              applySuggestions(
+                suggestions.filter((suggestion) =>
+                  selected.includes(suggestion.extension),
+                ),
+                outputFolder,
+                reorganize,
+              )
+            }
+          >
+            選択した提案を反映
+          </Button>
+        </Stack>
+      ) : (
+        <Text size="sm" c="dimmed">
+          提案はありません
+        </Text>
+      )}
+    </Card>
+  );
+}
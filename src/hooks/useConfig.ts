@@ -1,7 +1,30 @@
 import { useState, useEffect } from "react";
 import { invoke } from "@tauri-apps/api/core";
-import { open } from "@tauri-apps/plugin-dialog";
-import { Config } from "../types";
+import { open, save } from "@tauri-apps/plugin-dialog";
+import {
+  CatalogueMergeResult,
+  CollisionOptions,
+  Config,
+  ConfigChanged,
+  FallbackOptions,
+  FilePath,
+  FileResult,
+  FolderIssue,
+  ImportMode,
+  InputSource,
+  LoadedConfig,
+  ProfileList,
+  RuleImportResult,
+  SanitizeOptions,
+  SharedRuleSet,
+  Suggestion,
+  SuggestionApplyResult,
+} from "../types";
+
+/** ルールセットのファイル選択で使う形式 */
+const RULE_SET_FILTERS = [
+  { name: "ルールセット", extensions: ["json", "toml", "yaml", "yml"] },
+];
 
 /**
  * アプリケーション設定に関するカスタムフック
@@ -12,24 +35,93 @@ export function useConfig() {
   const [newCategory, setNewCategory] = useState("");
   const [newExtensions, setNewExtensions] = useState("");
   const [editCategory, setEditCategory] = useState<string | null>(null);
+  const [folderIssues, setFolderIssues] = useState<FolderIssue[]>([]);
+  const [profiles, setProfiles] = useState<ProfileList | null>(null);
+  const [sharedRuleSets, setSharedRuleSets] = useState<SharedRuleSet[]>([]);
+  const [suggestions, setSuggestions] = useState<Suggestion[]>([]);
 
   // 初期化時に設定を読み込む
   useEffect(() => {
     loadConfig();
   }, []);
 
+  /**
+   * 読み込んだ設定を反映する
+   * @param loaded load_config の戻り値
+   */
+  function applyLoadedConfig(loaded: LoadedConfig) {
+    setConfig(loaded.config);
+    setFolderIssues(loaded.folder_issues);
+    if (loaded.warnings.length > 0) {
+      alert(loaded.warnings.join("\n"));
+    }
+  }
+
   /**
    * 設定の読み込み
    */
   async function loadConfig() {
     try {
-      const config = await invoke<Config>("load_config");
-      setConfig(config);
+      applyLoadedConfig(await invoke<LoadedConfig>("load_config"));
+      await loadProfiles();
     } catch (error) {
       console.error("設定の読み込みエラー:", error);
     }
   }
 
+  /**
+   * 設定ファイルの変更を反映する
+   * 不正な内容に書き換えられた場合は反映せずにエラーを表示する
+   * @param changed config-changed イベントの内容
+   */
+  function applyConfigChanged(changed: ConfigChanged) {
+    if (changed.error || !changed.config) {
+      alert(changed.error ?? "設定ファイルの変更を反映できません");
+      return;
+    }
+    setConfig(changed.config);
+    setFolderIssues(changed.folder_issues);
+  }
+
+  /**
+   * プロファイル一覧の読み込み
+   */
+  async function loadProfiles() {
+    try {
+      setProfiles(await invoke<ProfileList>("list_profiles"));
+    } catch (error) {
+      console.error("プロファイルの読み込みエラー:", error);
+    }
+  }
+
+  /**
+   * プロファイルの切り替え
+   * @param name プロファイル名（nullの場合は既定のプロファイル）
+   */
+  async function switchProfile(name: string | null) {
+    try {
+      applyLoadedConfig(await invoke<LoadedConfig>("switch_profile", { name }));
+      await loadProfiles();
+    } catch (error) {
+      console.error("プロファイル切り替えエラー:", error);
+      alert(`エラーが発生しました: ${error}`);
+    }
+  }
+
+  /**
+   * プロファイルの削除
+   * @param name 削除するプロファイル名
+   */
+  async function deleteProfile(name: string) {
+    try {
+      await invoke("delete_profile", { name });
+      await loadProfiles();
+    } catch (error) {
+      console.error("プロファイル削除エラー:", error);
+      alert(`エラーが発生しました: ${error}`);
+    }
+  }
+
   /**
    * 設定の保存
    */
@@ -87,6 +179,425 @@ export function useConfig() {
     }
   }
 
+  /**
+   * 入力フォルダの設定を解除
+   */
+  async function clearInputFolder() {
+    if (!config) return;
+    try {
+      const updatedConfig = await invoke<Config>("clear_input_folder", {
+        config,
+      });
+      setConfig(updatedConfig);
+      await checkFolders(updatedConfig);
+    } catch (error) {
+      console.error("入力フォルダ解除エラー:", error);
+    }
+  }
+
+  /**
+   * 入力元を追加する（フォルダ選択ダイアログを表示）
+   */
+  async function addInputSource() {
+    if (!config) return;
+    try {
+      const folder = await open({ directory: true });
+      if (folder === null) return;
+
+      const source: InputSource = {
+        path: folder as string,
+        recursive: true,
+        output_folder: null,
+        categories: null,
+        enabled: true,
+      };
+      const updatedConfig = await invoke<Config>("add_input_source", {
+        source,
+        config,
+      });
+      setConfig(updatedConfig);
+      await checkFolders(updatedConfig);
+    } catch (error) {
+      console.error("入力元追加エラー:", error);
+      alert(`エラーが発生しました: ${error}`);
+    }
+  }
+
+  /**
+   * 入力元の設定を更新する（有効・無効の切り替えなど）
+   * @param source 更新後の入力元（同じパスの入力元を置き換える）
+   */
+  async function updateInputSource(source: InputSource) {
+    if (!config) return;
+    try {
+      const updatedConfig = await invoke<Config>("add_input_source", {
+        source,
+        config,
+      });
+      setConfig(updatedConfig);
+    } catch (error) {
+      console.error("入力元更新エラー:", error);
+      alert(`エラーが発生しました: ${error}`);
+    }
+  }
+
+  /**
+   * 入力元を削除する
+   * @param path 削除する入力元のパス
+   */
+  async function removeInputSource(path: string) {
+    if (!config) return;
+    try {
+      const updatedConfig = await invoke<Config>("remove_input_source", {
+        path,
+        config,
+      });
+      setConfig(updatedConfig);
+      await checkFolders(updatedConfig);
+    } catch (error) {
+      console.error("入力元削除エラー:", error);
+      alert(`エラーが発生しました: ${error}`);
+    }
+  }
+
+  /**
+   * カテゴリの出力先を設定する
+   * @param category カテゴリ名
+   * @param folder 出力先のフォルダ（nullの場合は既定の出力先に戻す）
+   */
+  async function setCategoryOutput(category: string, folder: string | null) {
+    if (!config) return;
+    try {
+      const updatedConfig = await invoke<Config>("set_category_output", {
+        category,
+        folder,
+        config,
+      });
+      setConfig(updatedConfig);
+      await checkFolders(updatedConfig);
+    } catch (error) {
+      console.error("カテゴリ出力先設定エラー:", error);
+      alert(`エラーが発生しました: ${error}`);
+    }
+  }
+
+  /**
+   * フォルダ選択ダイアログを表示して、カテゴリの出力先を設定する
+   * @param category カテゴリ名
+   */
+  async function selectCategoryOutput(category: string) {
+    try {
+      const folder = await open({ directory: true });
+      if (folder === null) return;
+      await setCategoryOutput(category, folder as string);
+    } catch (error) {
+      console.error("フォルダ選択エラー:", error);
+    }
+  }
+
+  /**
+   * 出力フォルダの削除
+   * @param folder 削除するフォルダのパス
+   */
+  async function removeOutputFolder(folder: string) {
+    if (!config) return;
+    try {
+      const updatedConfig = await invoke<Config>("remove_output_folder", {
+        folder,
+        config,
+      });
+      setConfig(updatedConfig);
+      await checkFolders(updatedConfig);
+    } catch (error) {
+      console.error("出力フォルダ削除エラー:", error);
+    }
+  }
+
+  /**
+   * 出力フォルダの並び替え
+   * @param folder 移動するフォルダのパス
+   * @param index 移動先の位置
+   */
+  async function moveOutputFolder(folder: string, index: number) {
+    if (!config) return;
+    try {
+      const updatedConfig = await invoke<Config>("move_output_folder", {
+        folder,
+        index,
+        config,
+      });
+      setConfig(updatedConfig);
+    } catch (error) {
+      console.error("出力フォルダ並び替えエラー:", error);
+    }
+  }
+
+  /**
+   * 保存済みフォルダの検証
+   * @param target 検証する設定（省略時は現在の設定）
+   */
+  async function checkFolders(target: Config | null = config) {
+    if (!target) return;
+    try {
+      const issues = await invoke<FolderIssue[]>("check_config_folders", {
+        config: target,
+      });
+      setFolderIssues(issues);
+    } catch (error) {
+      console.error("フォルダ検証エラー:", error);
+    }
+  }
+
+  /**
+   * どのカテゴリにも当てはまらないファイルの扱いを設定
+   * @param fallback 設定内容
+   */
+  async function setFallback(fallback: FallbackOptions) {
+    if (!config) return;
+    try {
+      const updatedConfig = await invoke<Config>("set_fallback", {
+        fallback,
+        config,
+      });
+      setConfig(updatedConfig);
+    } catch (error) {
+      console.error("未分類ファイル設定エラー:", error);
+      alert(`エラーが発生しました: ${error}`);
+    }
+  }
+
+  /**
+   * 移動先のファイル名の衝突の判定方法を設定
+   * @param collisions 設定内容
+   */
+  async function setCollisionOptions(collisions: CollisionOptions) {
+    if (!config) return;
+    try {
+      const updatedConfig = await invoke<Config>("set_collision_options", {
+        collisions,
+        config,
+      });
+      setConfig(updatedConfig);
+    } catch (error) {
+      console.error("同名ファイル設定エラー:", error);
+      alert(`エラーが発生しました: ${error}`);
+    }
+  }
+
+  /**
+   * コピー後に元ファイルを削除できなかった場合にコピーを取り消すかを設定
+   * @param enabled 取り消す場合はtrue
+   */
+  async function setRollbackRetainedCopies(enabled: boolean) {
+    if (!config) return;
+    try {
+      const updatedConfig = await invoke<Config>(
+        "set_rollback_retained_copies",
+        { enabled, config },
+      );
+      setConfig(updatedConfig);
+    } catch (error) {
+      console.error("移動設定エラー:", error);
+      alert(`エラーが発生しました: ${error}`);
+    }
+  }
+
+  /**
+   * 移動先のファイルシステムで使えない名前の扱いを設定
+   * @param sanitize 設定内容
+   */
+  async function setSanitizeOptions(sanitize: SanitizeOptions) {
+    if (!config) return;
+    try {
+      const updatedConfig = await invoke<Config>("set_sanitize_options", {
+        sanitize,
+        config,
+      });
+      setConfig(updatedConfig);
+    } catch (error) {
+      console.error("ファイル名設定エラー:", error);
+      alert(`エラーが発生しました: ${error}`);
+    }
+  }
+
+  /**
+   * 組み込みのカタログに追加された項目を取り込む
+   */
+  async function mergeCatalogue() {
+    if (!config) return;
+    try {
+      const result = await invoke<CatalogueMergeResult>("merge_catalogue", {
+        config,
+      });
+      setConfig(result.config);
+      alert(
+        result.added.length > 0
+          ? "次の拡張子を追加しました:\n" +
+              result.added
+                .map(
+                  (addition) =>
+                    `${addition.category}${addition.new_category ? "（新規）" : ""}: ${addition.extensions.join(", ")}`,
+                )
+                .join("\n")
+          : "追加できる項目はありません",
+      );
+    } catch (error) {
+      console.error("カタログ取り込みエラー:", error);
+      alert(`エラーが発生しました: ${error}`);
+    }
+  }
+
+  /**
+   * 未分類フォルダのファイルを分析して設定の提案を読み込む
+   * @param outputFolder 出力先フォルダ
+   */
+  async function loadSuggestions(outputFolder: string) {
+    if (!config) return;
+    try {
+      setSuggestions(
+        await invoke<Suggestion[]>("suggest_categories", {
+          outputFolder,
+          config,
+        }),
+      );
+    } catch (error) {
+      console.error("提案の読み込みエラー:", error);
+      alert(`エラーが発生しました: ${error}`);
+    }
+  }
+
+  /**
+   * 提案を設定に反映する
+   * @param selected 反映する提案
+   * @param outputFolder 出力先フォルダ
+   * @param reorganize 対象のファイルを整理し直すか
+   * @returns 整理し直した結果
+   */
+  async function applySuggestions(
+    selected: Suggestion[],
+    outputFolder: string,
+    reorganize: boolean,
+  ): Promise<FileResult[]> {
+    if (!config) return [];
+    try {
+      const result = await invoke<SuggestionApplyResult>("apply_suggestions", {
+        suggestions: selected,
+        outputFolder,
+        reorganize,
+        config,
+      });
+      setConfig(result.config);
+      setSuggestions(
+        suggestions.filter(
+          (suggestion) =>
+            !selected.some((s) => s.extension === suggestion.extension),
+        ),
+      );
+      return result.results;
+    } catch (error) {
+      console.error("提案の反映エラー:", error);
+      alert(`エラーが発生しました: ${error}`);
+      return [];
+    }
+  }
+
+  /**
+   * カテゴリのルールをファイルに書き出す
+   */
+  async function exportRuleSet() {
+    if (!config) return;
+    try {
+      const path = await save({
+        defaultPath: "shiwakekun-rules.json",
+        filters: RULE_SET_FILTERS,
+      });
+      if (path === null) return;
+      await invoke("export_rule_set", { path, config });
+    } catch (error) {
+      console.error("ルールセット書き出しエラー:", error);
+      alert(`エラーが発生しました: ${error}`);
+    }
+  }
+
+  /**
+   * ファイルからカテゴリのルールを読み込む
+   * @param mode 既存のルールへの追加か置き換えか
+   * @param path 読み込むファイル（省略時はファイルを選択）
+   */
+  async function importRuleSet(mode: ImportMode, path?: FilePath) {
+    if (!config) return;
+    try {
+      const file = path ?? (await open({ filters: RULE_SET_FILTERS }));
+      if (file === null) return;
+      const result = await invoke<RuleImportResult>("import_rule_set", {
+        path: file,
+        mode,
+        config,
+      });
+      setConfig(result.config);
+      if (result.conflicts.length > 0) {
+        alert(
+          "次のルールは既存のルールと競合したため反映しませんでした:\n" +
+            result.conflicts
+              .map(
+                (conflict) =>
+                  `${conflict.extension}: ${conflict.existing_category}（読み込み: ${conflict.imported_category}）`,
+              )
+              .join("\n"),
+        );
+      }
+    } catch (error) {
+      console.error("ルールセット読み込みエラー:", error);
+      alert(`エラーが発生しました: ${error}`);
+    }
+  }
+
+  /**
+   * 共有のルールセットを置くフォルダの選択
+   */
+  async function selectSharedRulesDir() {
+    if (!config) return;
+    try {
+      const folder = await open({ directory: true });
+      if (folder === null) return;
+      const updatedConfig = await invoke<Config>("set_shared_rules_dir", {
+        folder,
+        config,
+      });
+      setConfig(updatedConfig);
+      await loadSharedRuleSets(updatedConfig);
+    } catch (error) {
+      console.error("共有フォルダ選択エラー:", error);
+    }
+  }
+
+  /**
+   * 共有フォルダにあるルールセットの読み込み
+   * @param target 対象の設定（省略時は現在の設定）
+   */
+  async function loadSharedRuleSets(target: Config | null = config) {
+    if (!target) return;
+    try {
+      setSharedRuleSets(
+        await invoke<SharedRuleSet[]>("list_shared_rule_sets", {
+          config: target,
+        }),
+      );
+    } catch (error) {
+      console.error("共有ルールセット読み込みエラー:", error);
+      alert(`エラーが発生しました: ${error}`);
+    }
+  }
+
+  /**
+   * 共有フォルダのルールセットを反映する
+   * @param ruleSet 反映するルールセット
+   * @param mode 既存のルールへの追加か置き換えか
+   */
+  async function applySharedRuleSet(ruleSet: SharedRuleSet, mode: ImportMode) {
+    await importRuleSet(mode, ruleSet.path);
+  }
+
   /**
    * 拡張子の文字列を正規化して配列に変換
    * @param extensionsStr カンマ区切りの拡張子文字列
@@ -188,12 +699,40 @@ export function useConfig() {
     setNewExtensions,
     editCategory,
     setEditCategory,
+    folderIssues,
+    profiles,
+    sharedRuleSets,
+    suggestions,
     
     // アクション
     loadConfig,
     saveConfig,
+    applyConfigChanged,
     selectOutputFolder,
     selectInputFolder,
+    clearInputFolder,
+    addInputSource,
+    updateInputSource,
+    removeInputSource,
+    setCategoryOutput,
+    selectCategoryOutput,
+    removeOutputFolder,
+    moveOutputFolder,
+    checkFolders,
+    switchProfile,
+    deleteProfile,
+    setFallback,
+    setCollisionOptions,
+    setSanitizeOptions,
+    setRollbackRetainedCopies,
+    mergeCatalogue,
+    loadSuggestions,
+    applySuggestions,
+    exportRuleSet,
+    importRuleSet,
+    selectSharedRulesDir,
+    loadSharedRuleSets,
+    applySharedRuleSet,
     addCategory,
     updateCategory,
     deleteCategory,
@@ -1,19 +1,34 @@
 import { useState } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import { open } from "@tauri-apps/plugin-dialog";
-import { FileResult, OrganizeProgress, Config, TabType } from "../types";
+import {
+  FilePath,
+  FileResult,
+  OrganizeProgress,
+  OrganizeSummary,
+  ScanProgress,
+  ScanWarning,
+  SpaceCheck,
+  Config,
+  TabType,
+} from "../types";
+import { formatBytes } from "../utils/format";
 
 /**
  * ファイル操作に関するカスタムフック
  */
 export function useFiles() {
   // ファイル関連の状態
-  const [selectedFiles, setSelectedFiles] = useState<string[]>([]);
+  const [selectedFiles, setSelectedFiles] = useState<FilePath[]>([]);
   const [selectedOutputFolder, setSelectedOutputFolder] = useState<string>("");
   const [results, setResults] = useState<FileResult[]>([]);
   const [isProcessing, setIsProcessing] = useState(false);
   const [progress, setProgress] = useState<OrganizeProgress | null>(null);
+  const [summary, setSummary] = useState<OrganizeSummary | null>(null);
   const [isRecursive, setIsRecursive] = useState(true);
+  const [scanJobId, setScanJobId] = useState<string | null>(null);
+  const [scanProgress, setScanProgress] = useState<ScanProgress | null>(null);
+  const [scanWarnings, setScanWarnings] = useState<ScanWarning[]>([]);
 
   /**
    * ファイル選択ダイアログを表示して、ファイルを選択する
@@ -34,27 +49,68 @@ export function useFiles() {
   }
 
   /**
-   * 入力フォルダからファイルを読み込む
+   * 入力フォルダのスキャンを開始する
+   * 見つかったファイルは scan-progress イベントで順次追加される
    * @param config アプリケーション設定
-   * @returns 読み込まれたファイルパスの配列
+   * @param sources スキャンする入力元のパス（省略時は有効な入力元すべて）
+   * @returns スキャンを開始できたかどうか
    */
-  async function loadFilesFromInputFolder(config: Config | null) {
-    if (!config?.input_folder) {
+  async function loadFilesFromInputFolder(
+    config: Config | null,
+    sources?: string[],
+  ) {
+    if (!config?.input_folder && !config?.input_sources?.length) {
       alert("入力フォルダが設定されていません");
-      return [];
+      return false;
     }
 
     try {
-      const files = await invoke<string[]>("load_files_from_input_folder", {
+      setSelectedFiles([]);
+      setScanWarnings([]);
+      setScanProgress(null);
+      const jobId = await invoke<string>("scan_input_folder_async", {
         config,
         recursive: isRecursive,
+        sources: sources ?? null,
       });
-      setSelectedFiles(files);
-      return files;
+      setScanJobId(jobId);
+      return true;
     } catch (error) {
       console.error("ファイル読み込みエラー:", error);
       alert(`エラーが発生しました: ${error}`);
-      return [];
+      return false;
+    }
+  }
+
+  /**
+   * スキャンの進捗イベントを反映する
+   * @param progress スキャンの進捗
+   */
+  function applyScanProgress(progress: ScanProgress) {
+    setScanProgress(progress);
+    if (progress.files.length > 0) {
+      setSelectedFiles((prev) => [...prev, ...progress.files]);
+    }
+    if (progress.warnings.length > 0) {
+      setScanWarnings((prev) => [...prev, ...progress.warnings]);
+    }
+    if (progress.finished) {
+      setScanJobId(null);
+      if (progress.error) {
+        alert(`エラーが発生しました: ${progress.error}`);
+      }
+    }
+  }
+
+  /**
+   * スキャンを中断する
+   */
+  async function cancelScan() {
+    if (!scanJobId) return;
+    try {
+      await invoke("cancel_scan", { jobId: scanJobId });
+    } catch (error) {
+      console.error("スキャン中断エラー:", error);
     }
   }
 
@@ -70,12 +126,51 @@ export function useFiles() {
       return;
     }
 
+    // 移動先の空き容量を確認し、不足している場合は続けるか確認する
+    let force = false;
+    try {
+      const space = await invoke<SpaceCheck>("check_free_space", {
+        files: selectedFiles,
+        outputFolder: selectedOutputFolder,
+        config,
+      });
+      if (!space.sufficient) {
+        const breakdown = space.devices
+          .map(
+            (device) =>
+              `${device.path}: 必要 ${formatBytes(device.required_bytes)} / 空き ${
+                device.available_bytes != null
+                  ? formatBytes(device.available_bytes)
+                  : "不明"
+              }（${device.file_count}件）`,
+          )
+          .join("\n");
+        if (
+          !confirm(
+            `移動先の空き容量が不足しています。\n${breakdown}\n\n空き容量がなくなった時点で処理を止めます。続けますか？`,
+          )
+        ) {
+          return;
+        }
+        force = true;
+      }
+    } catch (error) {
+      console.error("空き容量確認エラー:", error);
+    }
+
     // 状態の初期化
     setIsProcessing(true);
     setResults([]);
+    setSummary(null);
     setProgress({
+      job_id: "",
       total_files: selectedFiles.length,
       processed_files: 0,
+      total_bytes: 0,
+      processed_bytes: 0,
+      current_file_bytes: 0,
+      current_file_total_bytes: 0,
+      bytes_per_second: 0,
       finished: false,
     });
     
@@ -85,12 +180,15 @@ export function useFiles() {
     }
 
     try {
-      // ファイル整理を実行
-      await invoke("organize_files_async", {
+      // ファイル整理を実行し、完了後に全ての結果で置き換える
+      const finalResults = await invoke<FileResult[]>("organize_files_async", {
         files: selectedFiles,
         outputFolder: selectedOutputFolder,
         config,
+        force,
       });
+      setResults(finalResults);
+      setIsProcessing(false);
     } catch (error) {
       console.error("ファイル整理エラー:", error);
       alert(`エラーが発生しました: ${error}`);
@@ -146,12 +244,19 @@ export function useFiles() {
     setIsProcessing,
     progress,
     setProgress,
+    summary,
+    setSummary,
     isRecursive,
     setIsRecursive,
+    isScanning: scanJobId !== null,
+    scanProgress,
+    scanWarnings,
     
     // アクション
     selectFiles,
     loadFilesFromInputFolder,
+    applyScanProgress,
+    cancelScan,
     organizeFiles,
     cancelProcessing,
     changeFilePermissions,
@@ -1,23 +1,312 @@
+export interface ScanOptions {
+  include_patterns: string[];
+  exclude_patterns: string[];
+  builtin_excludes: boolean;
+  use_ignore_files: boolean;
+  max_depth?: number | null;
+  follow_symlinks: boolean;
+  include_hidden: boolean;
+  same_file_system: boolean;
+}
+
 export interface Config {
+  version?: number;
   categories: Record<string, string[]>;
+  subcategories?: Record<string, Subcategory[]>;
+  fallback?: FallbackOptions;
+  extension_aliases?: Record<string, string>;
+  rename_rules?: Record<string, RenameRule[]>;
+  collisions?: CollisionOptions;
+  sanitize?: SanitizeOptions;
+  rollback_retained_copies?: boolean;
   output_folders: string[];
   input_folder?: string;
+  input_sources?: InputSource[];
+  category_outputs?: Record<string, string>;
+  scan?: ScanOptions;
+  shared_rules_dir?: string | null;
+  display_names?: Record<string, DisplayName>;
+  catalogue_extensions?: string[];
+}
+
+/**
+ * 移動先のファイル名の衝突の判定方法
+ */
+export interface CollisionOptions {
+  case_insensitive: boolean;
+  normalize_names: boolean;
+}
+
+export type SanitizeMode = "replace" | "reject";
+
+/**
+ * 移動先のファイルシステムで使えない名前の扱い
+ */
+export interface SanitizeOptions {
+  mode: SanitizeMode;
+  replacement: string;
+}
+
+/**
+ * 整理時のファイル名の変更ルール
+ */
+export type RenameRule =
+  | { type: "lowercase_extension" }
+  | { type: "replace_spaces"; with?: string }
+  | { type: "strip_duplicate_suffix" }
+  | { type: "normalize_unicode" }
+  | { type: "date_prefix"; format?: string };
+
+/**
+ * カテゴリの表示名
+ */
+export interface DisplayName {
+  ja: string;
+  en: string;
+}
+
+/**
+ * カタログから取り込んだカテゴリ
+ */
+export interface CatalogueAddition {
+  category: string;
+  new_category: boolean;
+  extensions: string[];
+}
+
+/**
+ * merge_catalogue の戻り値
+ */
+export interface CatalogueMergeResult {
+  config: Config;
+  added: CatalogueAddition[];
+}
+
+export type FallbackMode = "folder" | "by_extension" | "leave_in_place" | "review";
+
+/**
+ * どのカテゴリにも当てはまらないファイルの扱い
+ */
+export interface FallbackOptions {
+  mode: FallbackMode;
+  folder_name: string;
+  review_folder_name: string;
+}
+
+/**
+ * サブカテゴリのルール
+ */
+export interface Subcategory {
+  name: string;
+  extensions?: string[];
+  name_patterns?: string[];
+  children?: Subcategory[];
+}
+
+/**
+ * 振り分けルールのセット
+ */
+export interface RuleSet {
+  name?: string;
+  categories: Record<string, string[]>;
+  subcategories?: Record<string, Subcategory[]>;
+  extension_aliases?: Record<string, string>;
+}
+
+export type ImportMode = "merge" | "replace";
+
+/**
+ * ルールの競合
+ */
+export interface RuleConflict {
+  extension: string;
+  existing_category: string;
+  imported_category: string;
+}
+
+/**
+ * import_rule_set の戻り値
+ */
+export interface RuleImportResult {
+  config: Config;
+  conflicts: RuleConflict[];
+}
+
+/**
+ * 共有フォルダにあるルールセット
+ */
+export interface SharedRuleSet {
+  path: FilePath;
+  name: string;
+  rule_set?: RuleSet | null;
+  error?: string | null;
 }
 
+export type FolderIssueKind =
+  | "missing"
+  | "unmounted"
+  | "not_directory"
+  | "nested_in_output"
+  | "nested_in_input"
+  | "input_in_output";
+
+/**
+ * 保存済みフォルダの問題
+ */
+export interface FolderIssue {
+  path: string;
+  kind: FolderIssueKind;
+  message: string;
+}
+
+/**
+ * プロファイルの一覧
+ */
+export interface ProfileList {
+  active?: string | null;
+  profiles: string[];
+  config_path: string;
+  path_overridden: boolean;
+}
+
+/**
+ * 設定ファイルの変更通知
+ */
+export interface ConfigChanged {
+  config?: Config | null;
+  folder_issues: FolderIssue[];
+  error?: string | null;
+}
+
+/**
+ * load_config の戻り値
+ */
+export interface LoadedConfig {
+  config: Config;
+  folder_issues: FolderIssue[];
+  warnings: string[];
+}
+
+/**
+ * 入力元フォルダの設定
+ */
+export interface InputSource {
+  path: string;
+  recursive: boolean;
+  output_folder?: string | null;
+  categories?: Record<string, string[]> | null;
+  enabled: boolean;
+}
+
+/**
+ * バックエンドとやり取りするパス
+ * UTF-8として解釈できないパスは表示用文字列と元のバイト列（Base64）の組になる
+ */
+export type FilePath = string | { lossy: string; raw: string };
+
+export type FileOutcome = "moved" | "failed" | "skipped" | "source_retained";
+
 export interface FileResult {
-  file_path: string;
+  file_path: FilePath;
   success: boolean;
+  outcome: FileOutcome;
+  category?: string;
   message: string;
 }
 
+export type SuggestionReason = "catalogue" | "content" | "file_name";
+
+/**
+ * 未分類ファイルからの設定の変更の提案
+ */
+export interface Suggestion {
+  extension: string;
+  category: string;
+  reason: SuggestionReason;
+  files: FilePath[];
+}
+
+/**
+ * apply_suggestions の戻り値
+ */
+export interface SuggestionApplyResult {
+  config: Config;
+  results: FileResult[];
+}
+
+export interface OrganizeSummary {
+  job_id: string;
+  total_files: number;
+  succeeded: number;
+  failed: number;
+  skipped: number;
+  source_retained: number;
+  per_category: Record<string, number>;
+  elapsed_ms: number;
+  cancelled: boolean;
+  disk_full: boolean;
+}
+
+/**
+ * 移動先のデバイスごとの必要容量と空き容量
+ */
+export interface DeviceSpace {
+  path: string;
+  file_count: number;
+  required_bytes: number;
+  available_bytes?: number | null;
+}
+
+/**
+ * check_free_space の戻り値
+ */
+export interface SpaceCheck {
+  devices: DeviceSpace[];
+  sufficient: boolean;
+}
+
 export interface OrganizeProgress {
+  job_id: string;
   total_files: number;
   processed_files: number;
+  total_bytes: number;
+  processed_bytes: number;
+  current_file?: FilePath;
+  current_file_bytes: number;
+  current_file_total_bytes: number;
+  bytes_per_second: number;
+  eta_seconds?: number;
   current_result?: FileResult;
+  new_results?: FileResult[];
   finished: boolean;
   batch_progress?: boolean;
 }
 
+export interface ScanWarning {
+  path: FilePath;
+  message: string;
+}
+
+/**
+ * 入力フォルダの読み込み結果
+ */
+export interface LoadedFiles {
+  files: FilePath[];
+  warnings: ScanWarning[];
+}
+
+export interface ScanProgress {
+  job_id: string;
+  files: FilePath[];
+  files_found: number;
+  dirs_visited: number;
+  current_dir?: FilePath;
+  warnings: ScanWarning[];
+  finished: boolean;
+  cancelled: boolean;
+  error?: string;
+}
+
 export type TabType = "files" | "folders" | "results" | "settings" | "permissions";
 
 export interface PermissionStatus {
@@ -0,0 +1,25 @@
+import { FilePath } from "../types";
+
+// パス表示用の関数
+
+/**
+ * 表示用のパス文字列を取得
+ * UTF-8として解釈できないパスは置換文字で補った文字列を返す
+ */
+export function displayPath(path: FilePath): string {
+  return typeof path === "string" ? path : path.lossy;
+}
+
+/**
+ * パスからファイル名部分を取得
+ */
+export function fileName(path: FilePath): string {
+  return displayPath(path).split(/[\\/]/).pop() ?? "";
+}
+
+/**
+ * UTF-8として解釈できないパスかどうか
+ */
+export function isUndecodablePath(path: FilePath): boolean {
+  return typeof path !== "string";
+}